@@ -0,0 +1,45 @@
+use crate::models::*;
+use crate::{AsanaError, Client};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// The effort of a resource allocation, which can also be used as a model field type
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum Effort {
+    /// A percentage of the assignee's capacity
+    Percent(f64),
+    /// A number of hours over the allocated period
+    Hours(f64),
+}
+
+impl Client {
+    /// List all allocations, filtered by the parent project and/or the assignee
+    pub async fn allocations<T: Model>(&mut self, parent_gid: Option<&str>, assignee_gid: Option<&str>) -> Result<Vec<T>, AsanaError> {
+        if let Some(parent_gid) = parent_gid {
+            self.param("parent", parent_gid);
+        }
+        if let Some(assignee_gid) = assignee_gid {
+            self.param("assignee", assignee_gid);
+        }
+
+        self.list_all::<T>().await
+    }
+
+    /// Allocate a user to a project for a period of time
+    pub async fn create_allocation<T: Model>(&mut self, project_gid: &str, assignee_gid: &str, start_date: NaiveDate, end_date: NaiveDate, effort: Effort) -> Result<T, AsanaError> {
+        self.create::<T>(json!({
+            "parent": project_gid,
+            "assignee": assignee_gid,
+            "start_date": start_date.format("%Y-%m-%d").to_string(),
+            "end_date": end_date.format("%Y-%m-%d").to_string(),
+            "effort": effort,
+        })).await
+    }
+
+    /// Change the effort of an existing allocation
+    pub async fn set_allocation_effort<T: Model>(&mut self, allocation_gid: &str, effort: Effort) -> Result<T, AsanaError> {
+        self.update::<T>(allocation_gid, json!({ "effort": effort })).await
+    }
+}
@@ -0,0 +1,58 @@
+use crate::models::*;
+use crate::{AsanaError, Client};
+use serde_json::Value;
+use std::future::Future;
+
+/// The core operations of a [`Client`], so code can depend on a trait and use a fake in its tests
+///
+/// ```no_run
+/// use asana_sdk::*;
+/// use asana_sdk::models::Model;
+///
+/// model!(User "users" { name: String });
+///
+/// // Works with a real client, or any test double implementing the trait
+/// async fn user_names<A: AsanaApi>(api: &mut A) -> Result<Vec<String>, AsanaError> {
+///     let users = api.list::<User>().await?;
+///     Ok(users.iter().map(|user| user.name().clone()).collect())
+/// }
+/// ```
+pub trait AsanaApi {
+    fn get<T: Model + Send>(&mut self, gid: &str) -> impl Future<Output = Result<T, AsanaError>> + Send;
+    fn list<T: Model + Send>(&mut self) -> impl Future<Output = Result<Vec<T>, AsanaError>> + Send;
+    fn list_all<T: Model + Send>(&mut self) -> impl Future<Output = Result<Vec<T>, AsanaError>> + Send;
+    fn create<T: Model + Send>(&mut self, data: Value) -> impl Future<Output = Result<T, AsanaError>> + Send;
+    fn update<T: Model + Send>(&mut self, gid: &str, data: Value) -> impl Future<Output = Result<T, AsanaError>> + Send;
+    fn delete<T: Model + Send>(&mut self, gid: &str) -> impl Future<Output = Result<(), AsanaError>> + Send;
+    fn from<T: Model>(&mut self, relational_gid: &str) -> &mut Self;
+}
+
+impl AsanaApi for Client {
+    async fn get<T: Model + Send>(&mut self, gid: &str) -> Result<T, AsanaError> {
+        Client::get::<T>(self, gid).await
+    }
+
+    async fn list<T: Model + Send>(&mut self) -> Result<Vec<T>, AsanaError> {
+        Client::list::<T>(self).await
+    }
+
+    async fn list_all<T: Model + Send>(&mut self) -> Result<Vec<T>, AsanaError> {
+        Client::list_all::<T>(self).await
+    }
+
+    async fn create<T: Model + Send>(&mut self, data: Value) -> Result<T, AsanaError> {
+        Client::create::<T>(self, data).await
+    }
+
+    async fn update<T: Model + Send>(&mut self, gid: &str, data: Value) -> Result<T, AsanaError> {
+        Client::update::<T>(self, gid, data).await
+    }
+
+    async fn delete<T: Model + Send>(&mut self, gid: &str) -> Result<(), AsanaError> {
+        Client::delete::<T>(self, gid).await
+    }
+
+    fn from<T: Model>(&mut self, relational_gid: &str) -> &mut Self {
+        Client::from::<T>(self, relational_gid)
+    }
+}
@@ -0,0 +1,74 @@
+//! A blocking facade over the async [`Client`](crate::Client), for scripts & other sync contexts
+//!
+//! Enabled with the `blocking` feature. Each call runs the async client to completion on a
+//! single-threaded runtime owned by the blocking client, so this must not be used from within an async runtime.
+//!
+//! ```no_run
+//! use asana_sdk::*;
+//! use asana_sdk::models::Model;
+//!
+//! model!(User "users" { name: String });
+//!
+//! let mut asana = blocking::Client::connect(String::from("1/your:personal-access-token"));
+//! let user: User = asana.get::<User>("me").unwrap();
+//! ```
+
+use crate::models::*;
+use crate::{Asana, AsanaError};
+use serde_json::Value;
+use tokio::runtime::{Builder, Runtime};
+
+pub struct Client {
+    inner: crate::Client,
+    runtime: Runtime,
+}
+
+impl Client {
+    pub fn connect(token: String) -> Client {
+        Client::new(Asana::connect(token))
+    }
+
+    /// Wrap an async client, e.g. one configured through `Asana::builder(token)`
+    pub fn new(inner: crate::Client) -> Client {
+        let runtime = Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .expect("a runtime for the blocking client can be started");
+
+        Client { inner, runtime }
+    }
+
+    pub fn get<T: Model>(&mut self, gid: &str) -> Result<T, AsanaError> {
+        self.runtime.block_on(self.inner.get::<T>(gid))
+    }
+
+    pub fn list<T: Model>(&mut self) -> Result<Vec<T>, AsanaError> {
+        self.runtime.block_on(self.inner.list::<T>())
+    }
+
+    pub fn list_checked<T: Model>(&mut self) -> Result<(Vec<T>, bool), AsanaError> {
+        self.runtime.block_on(self.inner.list_checked::<T>())
+    }
+
+    pub fn list_all<T: Model>(&mut self) -> Result<Vec<T>, AsanaError> {
+        self.runtime.block_on(self.inner.list_all::<T>())
+    }
+
+    pub fn create<T: Model>(&mut self, data: Value) -> Result<T, AsanaError> {
+        self.runtime.block_on(self.inner.create::<T>(data))
+    }
+
+    pub fn update<T: Model>(&mut self, gid: &str, data: Value) -> Result<T, AsanaError> {
+        self.runtime.block_on(self.inner.update::<T>(gid, data))
+    }
+
+    pub fn delete<T: Model>(&mut self, gid: &str) -> Result<(), AsanaError> {
+        self.runtime.block_on(self.inner.delete::<T>(gid))
+    }
+
+    pub fn from<T: Model>(&mut self, relational_gid: &str) -> &mut Client {
+        self.inner.from::<T>(relational_gid);
+        self
+    }
+}
@@ -0,0 +1,182 @@
+use crate::cache::Cache;
+use crate::retry::RetryPolicy;
+use crate::{Client, Middleware};
+use reqwest::header::HeaderMap;
+use reqwest::Request;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Configures a [`Client`] before connecting, created with `Asana::builder(token)`
+pub struct ClientBuilder {
+    token: String,
+    opt_fields: Vec<String>,
+    enabled_changes: Vec<String>,
+    disabled_changes: Vec<String>,
+    dry_run: bool,
+    retry: RetryPolicy,
+    lenient: bool,
+    strict: bool,
+    cache: Option<(usize, Duration)>,
+    middleware: Vec<Middleware>,
+}
+
+impl ClientBuilder {
+    pub(crate) fn new(token: String) -> ClientBuilder {
+        ClientBuilder {
+            token,
+            opt_fields: Vec::new(),
+            enabled_changes: Vec::new(),
+            disabled_changes: Vec::new(),
+            dry_run: false,
+            retry: RetryPolicy::default(),
+            lenient: false,
+            strict: false,
+            cache: None,
+            middleware: Vec::new(),
+        }
+    }
+
+    /// Extra root fields to include on every call, which are deserialized into each model's `extra` map
+    pub fn opt_fields(mut self, fields: &[&str]) -> ClientBuilder {
+        self.opt_fields.extend(fields.iter().map(|f| f.to_string()));
+        self
+    }
+
+    /// Opt into API changes ahead of their rollout, sent as the `Asana-Enable` header on every call
+    pub fn enable_changes(mut self, changes: &[&str]) -> ClientBuilder {
+        self.enabled_changes.extend(changes.iter().map(|c| c.to_string()));
+        self
+    }
+
+    /// Opt out of API changes during their deprecation period, sent as the `Asana-Disable` header on every call
+    pub fn disable_changes(mut self, changes: &[&str]) -> ClientBuilder {
+        self.disabled_changes.extend(changes.iter().map(|c| c.to_string()));
+        self
+    }
+
+    /// Don't send writes (`POST`, `PUT`, `DELETE`), but return them as `AsanaError::DryRun` instead
+    ///
+    /// Reads are still sent, so code which fetches before writing keeps working.
+    ///
+    /// ```
+    /// # use asana_sdk::*;
+    /// # use asana_sdk::models::Model;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// model!(Task "tasks" { completed: bool });
+    ///
+    /// let mut asana = Asana::builder(String::from("1/your:personal-access-token"))
+    ///     .dry_run(true)
+    ///     .build();
+    ///
+    /// match asana.complete_task::<Task>("12345678").await {
+    ///     Err(AsanaError::DryRun(request)) => {
+    ///         assert_eq!(request.method, reqwest::Method::PUT);
+    ///         assert_eq!(request.body, Some(serde_json::json!({ "data": { "completed": true } })));
+    ///     }
+    ///     _ => panic!("expected a dry run"),
+    /// }
+    /// # }
+    /// ```
+    pub fn dry_run(mut self, dry_run: bool) -> ClientBuilder {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// How often to retry rate limited (`429`) and server error (`5xx`) responses, 3 by default
+    ///
+    /// Other client errors are never retried. Set to 0 to disable retries altogether.
+    ///
+    /// Server errors are only retried for reads, and for writes sent `with_idempotency_key()`, as Asana may have
+    /// applied a write before failing, and retrying it could e.g. create a task twice.
+    pub fn max_retries(mut self, max_retries: u32) -> ClientBuilder {
+        self.retry.max_retries = max_retries;
+        self
+    }
+
+    /// The delay before the first retry of a server error, doubled on each further attempt, 500ms by default
+    pub fn retry_base_delay(mut self, delay: Duration) -> ClientBuilder {
+        self.retry.base_delay = delay;
+        self
+    }
+
+    /// The upper bound for the delay between retries of a server error, 30s by default
+    pub fn retry_max_delay(mut self, delay: Duration) -> ClientBuilder {
+        self.retry.max_delay = delay;
+        self
+    }
+
+    /// Skip list items which fail to deserialize, instead of failing the whole list call
+    ///
+    /// Skipped items and their errors can be inspected with `skipped_items()` after each list call.
+    pub fn lenient(mut self, lenient: bool) -> ClientBuilder {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Fail on fields which a model doesn't declare, instead of collecting them into its `extra` map
+    ///
+    /// Fields requested through `opt_fields()` or `with_opt_fields()` are still allowed in `extra`.
+    /// Combined with `lenient()`, list items with unexpected fields are skipped instead.
+    pub fn strict(mut self, strict: bool) -> ClientBuilder {
+        self.strict = strict;
+        self
+    }
+
+    /// Cache up to `capacity` entities fetched with `get()` in memory, for `ttl` after they were fetched
+    ///
+    /// Clients forked for concurrent calls share the cache. Entities changed with `update()` or `delete()`
+    /// are dropped from it, and a single call can skip it with `without_cache()`.
+    /// Changes made any other way (or by anyone else) only show up once the cached copy expires.
+    pub fn cache(mut self, capacity: usize, ttl: Duration) -> ClientBuilder {
+        self.cache = Some((capacity, ttl));
+        self
+    }
+
+    /// Change every request before it's sent, e.g. to add headers, or to log or count calls
+    ///
+    /// Middleware runs in the order it was added, once per call (retries resend the same request),
+    /// and also sees the writes which are held back in dry-run mode.
+    /// ```no_run
+    /// # use asana_sdk::*;
+    /// let mut asana = Asana::builder(String::from("1/your:personal-access-token"))
+    ///     .middleware(|mut request| {
+    ///         request.headers_mut().insert("X-Request-Source", "nightly-sync".parse().unwrap());
+    ///         request
+    ///     })
+    ///     .build();
+    /// ```
+    pub fn middleware(mut self, middleware: impl Fn(Request) -> Request + Send + Sync + 'static) -> ClientBuilder {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    pub fn build(self) -> Client {
+        Client {
+            token: self.token,
+            endpoint: String::from(""),
+            params: Vec::new(),
+            headers: Vec::new(),
+            opt_fields: self.opt_fields,
+            next_opt_fields: None,
+            last_headers: HeaderMap::new(),
+            enabled_changes: self.enabled_changes,
+            disabled_changes: self.disabled_changes,
+            dry_run: self.dry_run,
+            retry: self.retry,
+            lenient: self.lenient,
+            strict: self.strict,
+            requested_extra: Vec::new(),
+            skipped: Vec::new(),
+            cache: self.cache.map(|(capacity, ttl)| Arc::new(Mutex::new(Cache::new(capacity, ttl)))),
+            bypass_cache: false,
+            traced_model: None,
+            presets: HashMap::new(),
+            middleware: self.middleware,
+            client: reqwest::Client::builder()
+                .user_agent("asana_sdk.rs/0.1.2")
+                .build().unwrap(),
+        }
+    }
+}
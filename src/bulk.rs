@@ -0,0 +1,173 @@
+use crate::models::*;
+use crate::{AsanaError, Client};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use serde_json::Value;
+use std::future::Future;
+use std::collections::HashMap;
+
+/// How many requests bulk operations keep in flight, well below Asana's concurrency limits
+const CONCURRENCY: usize = 10;
+
+/// The outcome of a bulk operation, with successes and failures keyed by gid
+#[derive(Debug)]
+pub struct MultiResult<T> {
+    pub succeeded: HashMap<String, T>,
+    pub failed: HashMap<String, AsanaError>,
+}
+
+impl<T> MultiResult<T> {
+    /// Whether every item of the operation succeeded
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    /// The total number of items in the operation
+    pub fn len(&self) -> usize {
+        self.succeeded.len() + self.failed.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn collect(results: Vec<(String, Result<T, AsanaError>)>) -> MultiResult<T> {
+        let mut multi = MultiResult { succeeded: HashMap::new(), failed: HashMap::new() };
+        for (gid, result) in results {
+            match result {
+                Ok(item) => { multi.succeeded.insert(gid, item); }
+                Err(e) => { multi.failed.insert(gid, e); }
+            }
+        }
+
+        multi
+    }
+}
+
+impl Client {
+    /// Fetch many entities by gid concurrently, reporting which gids failed and why
+    pub async fn get_many<T: Model>(&mut self, gids: &[&str]) -> MultiResult<T> {
+        self.for_each_gid(gids, |mut client, gid| async move { client.get::<T>(gid).await }).await
+    }
+
+    /// Update many entities concurrently, each with its own fields, reporting which gids failed and why
+    ///
+    /// Unlike a batch request, there's no limit on the number of updates, and each is an ordinary `update()`.
+    /// The results are keyed by gid, so a gid appearing more than once fails with `AsanaError::Validation`,
+    /// without sending any of its updates.
+    ///
+    /// In dry-run mode, every update fails with its own request:
+    /// ```
+    /// # use asana_sdk::*;
+    /// # use asana_sdk::models::Model;
+    /// # use serde_json::json;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// model!(Task "tasks" { name: String });
+    ///
+    /// let mut asana = Asana::builder(String::from("1/your:personal-access-token")).dry_run(true).build();
+    /// let updates = vec![
+    ///     (String::from("12345678"), json!({ "name": "Write docs" })),
+    ///     (String::from("23456789"), json!({ "completed": true })),
+    /// ];
+    /// let result = asana.update_many::<Task>(updates).await;
+    ///
+    /// assert!(result.succeeded.is_empty());
+    /// match &result.failed["23456789"] {
+    ///     AsanaError::DryRun(request) => assert_eq!(request.body, Some(json!({ "data": { "completed": true } }))),
+    ///     _ => panic!("expected a dry run"),
+    /// }
+    ///
+    /// let duplicated = vec![
+    ///     (String::from("12345678"), json!({ "name": "Write docs" })),
+    ///     (String::from("12345678"), json!({ "name": "Write tests" })),
+    /// ];
+    /// let result = asana.update_many::<Task>(duplicated).await;
+    /// assert!(matches!(result.failed["12345678"], AsanaError::Validation(_)));
+    /// # }
+    /// ```
+    pub async fn update_many<T: Model>(&mut self, updates: Vec<(String, Value)>) -> MultiResult<T> {
+        let mut changes: HashMap<&str, Vec<&Value>> = HashMap::new();
+        for (gid, data) in &updates {
+            changes.entry(gid.as_str()).or_default().push(data);
+        }
+
+        let (unique, duplicated): (Vec<_>, Vec<_>) = changes.into_iter().partition(|(_, changes)| changes.len() == 1);
+        let gids: Vec<&str> = unique.iter().map(|(gid, _)| *gid).collect();
+        let changes: HashMap<&str, &Value> = unique.into_iter().map(|(gid, changes)| (gid, changes[0])).collect();
+
+        let mut result = self.for_each_gid(&gids, |mut client, gid| {
+            let data = changes[gid].clone();
+            async move { client.update::<T>(gid, data).await }
+        }).await;
+
+        for (gid, changes) in duplicated {
+            let error = AsanaError::Validation(format!("{} has {} updates, but can only be updated once", gid, changes.len()));
+            result.failed.insert(gid.to_string(), error);
+        }
+
+        result
+    }
+
+    /// Add a tag to many tasks concurrently, reporting which tasks failed and why
+    pub async fn add_tag_to_tasks(&mut self, tag_gid: &str, task_gids: &[&str]) -> MultiResult<()> {
+        self.for_each_gid(task_gids, |mut client, gid| async move { client.add_tag_to_task(gid, tag_gid).await }).await
+    }
+
+    /// Remove a tag from many tasks concurrently, reporting which tasks failed and why
+    pub async fn remove_tag_from_tasks(&mut self, tag_gid: &str, task_gids: &[&str]) -> MultiResult<()> {
+        self.for_each_gid(task_gids, |mut client, gid| async move { client.remove_tag_from_task(gid, tag_gid).await }).await
+    }
+
+    /// List all entities related to each of the given parents concurrently, keyed by parent gid
+    ///
+    /// Like `asana.from::<P>(gid).list_all::<T>()` for each parent, so every page is fetched.
+    /// Fails with the first error, as any missing parent would make the result incomplete.
+    /// ```no_run
+    /// # use asana_sdk::*;
+    /// # use asana_sdk::models::Model;
+    /// # async fn example() -> Result<(), AsanaError> {
+    /// # let mut asana = Asana::connect(String::from("1/your:personal-access-token"));
+    /// model!(Section "sections" {});
+    /// model!(Task "tasks" { name: String });
+    ///
+    /// let tasks = asana.list_from_many::<Section, Task>(&["12345678", "23456789"]).await?;
+    /// for (section, tasks) in tasks {
+    ///     println!("{}: {} tasks", section, tasks.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_from_many<P: Model, T: Model>(&mut self, parent_gids: &[&str]) -> Result<HashMap<String, Vec<T>>, AsanaError> {
+        self.list_from_many_at::<T>(&P::endpoint(), parent_gids).await
+    }
+
+    pub(crate) async fn list_from_many_at<T: Model>(&self, parent_endpoint: &str, parent_gids: &[&str]) -> Result<HashMap<String, Vec<T>>, AsanaError> {
+        stream::iter(parent_gids)
+            .map(|gid| {
+                let mut client = self.fork();
+                async move {
+                    let data = client.scope(parent_endpoint, gid).list_all::<T>().await?;
+                    Ok((gid.to_string(), data))
+                }
+            })
+            .buffer_unordered(CONCURRENCY)
+            .try_collect().await
+    }
+
+    /// Run a call for each gid on its own forked client, keeping at most `CONCURRENCY` calls in flight
+    async fn for_each_gid<'a, F, Fut, R>(&self, gids: &'a [&'a str], call: F) -> MultiResult<R>
+    where
+        F: Fn(Client, &'a str) -> Fut,
+        Fut: Future<Output = Result<R, AsanaError>>,
+    {
+        let results = stream::iter(gids)
+            .map(|gid| {
+                let result = call(self.fork(), gid);
+                async move { (gid.to_string(), result.await) }
+            })
+            .buffer_unordered(CONCURRENCY)
+            .collect::<Vec<_>>().await;
+
+        MultiResult::collect(results)
+    }
+}
@@ -0,0 +1,116 @@
+use crate::models::*;
+use crate::{opt_fields, parse, AsanaError, Client};
+use reqwest::Method;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// An in-memory cache of fetched entities, which evicts the least recently used entity once full
+///
+/// Entities are keyed by their path (`{endpoint}/{gid}`) and the requested fields,
+/// so models with different field sets don't see each other's cached data.
+pub(crate) struct Cache {
+    capacity: usize,
+    ttl: Duration,
+    entries: HashMap<(String, String), Entry>,
+    tick: u64,
+}
+
+struct Entry {
+    data: Value,
+    fetched: Instant,
+    used: u64,
+}
+
+impl Cache {
+    pub(crate) fn new(capacity: usize, ttl: Duration) -> Cache {
+        Cache { capacity, ttl, entries: HashMap::new(), tick: 0 }
+    }
+
+    fn get(&mut self, path: &str, fields: &str) -> Option<Value> {
+        self.tick += 1;
+        let key = (path.to_string(), fields.to_string());
+
+        match self.entries.get_mut(&key) {
+            Some(entry) if entry.fetched.elapsed() < self.ttl => {
+                entry.used = self.tick;
+                Some(entry.data.clone())
+            }
+            Some(_) => {
+                self.entries.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&mut self, path: String, fields: String, data: Value) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        self.tick += 1;
+        let key = (path, fields);
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            let oldest = self.entries.iter().min_by_key(|(_, entry)| entry.used).map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+
+        self.entries.insert(key, Entry { data, fetched: Instant::now(), used: self.tick });
+    }
+
+    /// Drop every cached copy of an entity, whichever fields were requested
+    fn invalidate(&mut self, path: &str) {
+        self.entries.retain(|(cached, _), _| cached != path);
+    }
+}
+
+impl Client {
+    /// Skip the cache for the next `get()` only, fetching a fresh copy of the entity (which is then cached)
+    pub fn without_cache(&mut self) -> &mut Client {
+        self.bypass_cache = true;
+        self
+    }
+
+    /// Get an entity from the cache if it's still fresh, or fetch and cache it otherwise
+    pub(crate) async fn get_cached<T: Model>(&mut self, cache: &Mutex<Cache>, gid: &str, read: bool) -> Result<T, AsanaError> {
+        let path = format!("{}/{}", T::endpoint(), gid);
+        let fields = opt_fields::<T>(self.next_opt_fields.as_ref().unwrap_or(&self.opt_fields));
+
+        let cached = if read { cache.lock().unwrap().get(&path, &fields) } else { None };
+        let data = match cached {
+            Some(data) => {
+                // Consume the call state, like a request would
+                self.requested_extra = self.next_opt_fields.take().unwrap_or_else(|| self.opt_fields.clone());
+                self.headers.clear();
+                data
+            }
+            None => {
+                let response = self.call::<T>(Method::GET, Some(gid), None).await?;
+                let model: Wrapper<Value> = parse(response).await?;
+                cache.lock().unwrap().insert(path, fields, model.data.clone());
+                model.data
+            }
+        };
+
+        let model = T::from_value(data)?;
+        self.check_strict(&model)?;
+
+        Ok(model)
+    }
+
+    /// Drop an entity from the cache, after it was changed through this client
+    pub(crate) fn invalidate_cached<T: Model>(&self, gid: &str) {
+        self.invalidate_cached_path(&format!("{}/{}", T::endpoint(), gid));
+    }
+
+    /// Drop an entity from the cache by its `endpoint/gid` path, for writes which aren't tied to a model
+    pub(crate) fn invalidate_cached_path(&self, path: &str) {
+        if let Some(cache) = &self.cache {
+            cache.lock().unwrap().invalidate(path);
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use crate::models::*;
+use crate::{parse, AsanaError, Client};
+use reqwest::Method;
+use serde_json::{json, Value};
+
+impl Client {
+    /// Add an option to an `enum` custom field, at the end of its options
+    pub async fn add_enum_option(&mut self, field_gid: &str, name: &str, color: Option<&str>) -> Result<EnumOption, AsanaError> {
+        let mut body = json!({ "name": name });
+        if let Some(color) = color {
+            body["color"] = json!(color);
+        }
+
+        self.enum_option_action(field_gid, "enum_options", body).await
+    }
+
+    /// Move an option of an `enum` custom field before another option, or to the end by passing `None`
+    pub async fn reorder_enum_option(&mut self, field_gid: &str, option_gid: &str, before: Option<&str>) -> Result<EnumOption, AsanaError> {
+        let body = match before {
+            Some(before) => json!({ "enum_option": option_gid, "before_enum_option": before }),
+            None => {
+                // Like sections, moving to the end means moving after the current last option
+                let options: Vec<EnumOption> = self.relation("custom_fields", field_gid, "enum_options", &["name", "color", "enabled"]).await?;
+                match options.iter().rev().find(|option| option.gid != option_gid) {
+                    Some(last) => json!({ "enum_option": option_gid, "after_enum_option": last.gid }),
+                    None => {
+                        return options.into_iter()
+                            .find(|option| option.gid == option_gid)
+                            .ok_or_else(|| AsanaError::Validation(format!("{} is not an option of custom field {}", option_gid, field_gid)));
+                    }
+                }
+            }
+        };
+
+        self.enum_option_action(field_gid, "enum_options/insert", body).await
+    }
+
+    async fn enum_option_action(&mut self, field_gid: &str, action: &str, body: Value) -> Result<EnumOption, AsanaError> {
+        let path = format!("custom_fields/{}/{}", field_gid, action);
+        let response = self.request(Method::POST, &path, None, Some(body)).await?;
+        let option: Wrapper<EnumOption> = parse(response).await?;
+
+        Ok(option.data)
+    }
+}
@@ -0,0 +1,87 @@
+//! Deserialization helpers for fields which Asana encodes inconsistently
+//!
+//! These are meant to be used with serde's `deserialize_with` attribute on a model field.
+
+use serde::de;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NumberOrString<T> {
+    Number(T),
+    String(String),
+}
+
+/// Deserialize a number which may arrive as a JSON number, a numeric string, or null
+///
+/// Custom field `number_value`s are returned as strings or numbers depending on their precision settings.
+/// Empty strings and null are deserialized into `None`. Combine with `#[serde(default)]` for fields which may be absent.
+///
+/// ```
+/// # use asana_sdk::model;
+/// # use asana_sdk::models::Model;
+/// model!(CustomField "custom_fields" {
+///     #[serde(default, deserialize_with = "asana_sdk::de::number_or_string")]
+///     number_value: Option<f64>,
+/// });
+///
+/// let number: CustomField = serde_json::from_str(r#"{"gid": "1", "resource_type": "custom_field", "number_value": 1.5}"#).unwrap();
+/// let string: CustomField = serde_json::from_str(r#"{"gid": "1", "resource_type": "custom_field", "number_value": "1.50"}"#).unwrap();
+/// let empty: CustomField = serde_json::from_str(r#"{"gid": "1", "resource_type": "custom_field", "number_value": null}"#).unwrap();
+///
+/// assert_eq!(number.number_value(), &Some(1.5));
+/// assert_eq!(string.number_value(), &Some(1.5));
+/// assert_eq!(empty.number_value(), &None);
+/// ```
+pub fn number_or_string<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + FromStr,
+    T::Err: Display,
+{
+    match Option::<NumberOrString<T>>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(NumberOrString::Number(number)) => Ok(Some(number)),
+        Some(NumberOrString::String(string)) if string.trim().is_empty() => Ok(None),
+        Some(NumberOrString::String(string)) => string.trim().parse().map(Some).map_err(de::Error::custom),
+    }
+}
+
+/// A count computed by Asana (like `num_subtasks`), which may arrive as a JSON number or a numeric string
+///
+/// Unlike [`number_or_string`], this is a type rather than a `deserialize_with` function, so macros like the
+/// `..TaskFields` preset can refer to it through `$crate`, even when this crate is renamed as a dependency.
+/// Use it as `Option<Count>`, which also accepts null.
+/// ```
+/// use asana_sdk::de::Count;
+///
+/// assert_eq!(serde_json::from_str::<Count>("3").unwrap(), Count(3));
+/// assert_eq!(serde_json::from_str::<Count>(r#""3""#).unwrap(), Count(3));
+/// assert_eq!(u32::from(Count(3)), 3);
+/// ```
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(transparent)]
+pub struct Count(pub u32);
+
+impl<'de> Deserialize<'de> for Count {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match NumberOrString::<u32>::deserialize(deserializer)? {
+            NumberOrString::Number(count) => Ok(Count(count)),
+            NumberOrString::String(string) => string.trim().parse().map(Count).map_err(de::Error::custom),
+        }
+    }
+}
+
+impl From<Count> for u32 {
+    fn from(count: Count) -> u32 {
+        count.0
+    }
+}
+
+impl Display for Count {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
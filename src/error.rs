@@ -0,0 +1,113 @@
+use crate::DryRun;
+use reqwest::{Response, StatusCode};
+use serde::Deserialize;
+use std::fmt;
+
+/// Errors returned by API calls
+#[derive(Debug)]
+pub enum AsanaError {
+    /// The request could not be sent, or the response could not be read
+    Http(reqwest::Error),
+    /// The Asana API responded with a non-success status code
+    Api { status: StatusCode, message: String },
+    /// The entity was changed concurrently (`409 Conflict`), so the change should be re-applied to a fresh copy
+    Conflict { message: String },
+    /// An events request had no sync token, or an expired one (`412 Precondition Failed`), with a fresh token to resume from
+    InvalidSync { sync: String },
+    /// The response body did not match the requested model, with the raw body when the whole response failed to parse
+    Deserialize { error: serde_json::Error, body: Option<String> },
+    /// The request was invalid, and was not sent
+    Validation(String),
+    /// Writing results out, e.g. during an export, failed
+    Io(std::io::Error),
+    /// A write request which was not sent, because the client is in dry-run mode
+    DryRun(DryRun),
+    /// A multi-step operation failed halfway, and undoing its earlier steps failed too
+    RollbackFailed { error: Box<AsanaError>, rollback: Box<AsanaError> },
+}
+
+#[derive(Deserialize)]
+struct ErrorList {
+    errors: Vec<ErrorMessage>,
+    #[serde(default)]
+    sync: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ErrorMessage {
+    message: String,
+}
+
+impl AsanaError {
+    /// Build an `Api` error from a failed response, using the messages from Asana's `errors` body
+    pub(crate) async fn from_response(response: Response) -> AsanaError {
+        let status = response.status();
+        let body = match response.text().await {
+            Ok(body) => body,
+            Err(e) => return AsanaError::Http(e),
+        };
+
+        let message = match serde_json::from_str::<ErrorList>(&body) {
+            Ok(ErrorList { sync: Some(sync), .. }) if status == StatusCode::PRECONDITION_FAILED => {
+                return AsanaError::InvalidSync { sync };
+            }
+            Ok(list) => list.errors.into_iter().map(|e| e.message).collect::<Vec<_>>().join("; "),
+            Err(_) => body,
+        };
+
+        match status {
+            StatusCode::CONFLICT => AsanaError::Conflict { message },
+            status => AsanaError::Api { status, message },
+        }
+    }
+}
+
+impl fmt::Display for AsanaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AsanaError::Http(e) => write!(f, "HTTP error: {}", e),
+            AsanaError::Api { status, message } => write!(f, "Asana API returned {}: {}", status, message),
+            AsanaError::Conflict { message } => write!(f, "Conflicting concurrent edit: {}", message),
+            AsanaError::InvalidSync { .. } => write!(f, "Missing or expired events sync token"),
+            AsanaError::Deserialize { error, .. } => write!(f, "Could not deserialize response: {}", error),
+            AsanaError::Validation(message) => write!(f, "Invalid request: {}", message),
+            AsanaError::Io(e) => write!(f, "IO error: {}", e),
+            AsanaError::DryRun(request) => write!(f, "Dry run, {} {} was not sent", request.method, request.url),
+            AsanaError::RollbackFailed { error, rollback } => write!(f, "{}, and rolling back failed: {}", error, rollback),
+        }
+    }
+}
+
+impl std::error::Error for AsanaError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AsanaError::Http(e) => Some(e),
+            AsanaError::Api { .. } => None,
+            AsanaError::Conflict { .. } => None,
+            AsanaError::InvalidSync { .. } => None,
+            AsanaError::Deserialize { error, .. } => Some(error),
+            AsanaError::Validation(_) => None,
+            AsanaError::Io(e) => Some(e),
+            AsanaError::DryRun(_) => None,
+            AsanaError::RollbackFailed { error, .. } => Some(error.as_ref()),
+        }
+    }
+}
+
+impl From<reqwest::Error> for AsanaError {
+    fn from(e: reqwest::Error) -> Self {
+        AsanaError::Http(e)
+    }
+}
+
+impl From<std::io::Error> for AsanaError {
+    fn from(e: std::io::Error) -> Self {
+        AsanaError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for AsanaError {
+    fn from(e: serde_json::Error) -> Self {
+        AsanaError::Deserialize { error: e, body: None }
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{parse, AsanaError, Client};
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::marker::PhantomData;
+
+/// Polls the events of a resource (like a project or task), created with `events()`
+///
+/// Each poll returns the events since the previous one, following Asana's sync tokens.
+/// ```no_run
+/// # use asana_sdk::*;
+/// # async fn example() -> Result<(), AsanaError> {
+/// # let mut asana = Asana::connect(String::from("1/your:personal-access-token"));
+/// let mut events = asana.events::<serde_json::Value>("12345678", None);
+/// loop {
+///     match events.poll().await? {
+///         EventsPoll::Events(events) => println!("{} new events", events.len()),
+///         EventsPoll::Gap(_) => println!("Events were missed, resyncing everything"),
+///     }
+///
+///     tokio::time::delay_for(std::time::Duration::from_secs(30)).await;
+/// }
+/// # }
+/// ```
+pub struct Events<'a, T> {
+    client: &'a mut Client,
+    resource: String,
+    sync: Option<String>,
+    model: PhantomData<T>,
+}
+
+/// The outcome of polling for events
+#[derive(Debug)]
+pub enum EventsPoll<T> {
+    /// The events since the previous poll, which is empty for the very first poll
+    Events(Vec<T>),
+    /// The sync token expired, so events since the previous poll are lost, and polling resumes from now on
+    Gap(EventsGap),
+}
+
+/// A gap in the events of a resource, after which a consumer should do a full resync
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventsGap {
+    pub new_sync: String,
+}
+
+#[derive(Deserialize)]
+struct EventsPage<T> {
+    data: Vec<T>,
+    sync: String,
+    #[serde(default)]
+    has_more: bool,
+}
+
+impl<'a, T: DeserializeOwned> Events<'a, T> {
+    /// Fetch the events since the previous poll
+    pub async fn poll(&mut self) -> Result<EventsPoll<T>, AsanaError> {
+        let mut events = Vec::new();
+
+        loop {
+            self.client.param("resource", &self.resource);
+            if let Some(sync) = &self.sync {
+                self.client.params.push((String::from("sync"), sync.clone()));
+            }
+
+            let page: EventsPage<T> = match self.client.request(Method::GET, "events", None, None).await {
+                Ok(response) => parse(response).await?,
+                // Asana hands out the first token as an error, so only a rejected token means a gap
+                Err(AsanaError::InvalidSync { sync }) => {
+                    let expired = self.sync.replace(sync.clone()).is_some();
+                    return Ok(if expired { EventsPoll::Gap(EventsGap { new_sync: sync }) } else { EventsPoll::Events(events) });
+                }
+                Err(e) => return Err(e),
+            };
+
+            self.sync = Some(page.sync);
+            events.extend(page.data);
+
+            if !page.has_more {
+                return Ok(EventsPoll::Events(events));
+            }
+        }
+    }
+
+    /// The current sync token, which can be stored to resume polling later with `events()`
+    pub fn sync_token(&self) -> Option<&str> {
+        self.sync.as_deref()
+    }
+}
+
+impl Client {
+    /// Poll the events of a resource, optionally resuming from a stored sync token
+    pub fn events<T: DeserializeOwned>(&mut self, resource_gid: &str, sync: Option<&str>) -> Events<'_, T> {
+        Events {
+            client: self,
+            resource: resource_gid.to_string(),
+            sync: sync.map(String::from),
+            model: PhantomData,
+        }
+    }
+}
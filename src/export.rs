@@ -0,0 +1,65 @@
+use crate::models::*;
+use crate::{AsanaError, Client};
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use std::marker::PhantomData;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+/// An entity as returned by Asana, checked against the model `T` but kept as JSON
+///
+/// Serializing a model drops its `#[readonly]` fields, which are meant for writes, so exports keep the response as-is.
+struct Raw<T> {
+    value: Value,
+    extra: Vec<String>,
+    model: PhantomData<fn() -> T>,
+}
+
+impl<'de, T: Model> Deserialize<'de> for Raw<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        let model = T::from_value(value.clone()).map_err(serde::de::Error::custom)?;
+        let extra = model.extra_keys().into_iter().map(String::from).collect();
+
+        Ok(Raw { value, extra, model: PhantomData })
+    }
+}
+
+impl<T: Model> Model for Raw<T> {
+    fn endpoint() -> String { T::endpoint() }
+
+    fn gid(&self) -> &str { self.value["gid"].as_str().unwrap_or_default() }
+
+    fn field_names() -> &'static [&'static str] { T::field_names() }
+
+    fn opt_strings() -> Vec<String> { T::opt_strings() }
+
+    fn extra_keys(&self) -> Vec<&str> {
+        self.extra.iter().map(String::as_str).collect()
+    }
+}
+
+impl Client {
+    /// Write every entity of a list endpoint as newline-delimited JSON, returning the number of entities written
+    ///
+    /// Pages are written as they arrive, so only one page is held in memory at a time.
+    /// Each entity is checked against the model, but written as returned by Asana, including `#[readonly]` fields.
+    /// Like `list_all()`, this can be scoped with `from()`, e.g. `asana.from::<Project>(gid).export_ndjson::<Task, _>(file)`.
+    pub async fn export_ndjson<T: Model, W: AsyncWrite + Unpin>(&mut self, mut writer: W) -> Result<usize, AsanaError> {
+        let mut pages = self.pages::<Raw<T>>();
+        let mut count = 0;
+
+        while let Some(page) = pages.next_page().await {
+            let mut lines = Vec::new();
+            for item in page? {
+                serde_json::to_writer(&mut lines, &item.value).map_err(std::io::Error::from)?;
+                lines.push(b'\n');
+                count += 1;
+            }
+
+            writer.write_all(&lines).await?;
+        }
+
+        writer.flush().await?;
+        Ok(count)
+    }
+}
@@ -0,0 +1,53 @@
+use crate::models::*;
+use crate::{AsanaError, Client};
+use serde_json::json;
+
+impl Client {
+    /// List all time periods (like quarters & fiscal years) in a workspace, following every page
+    ///
+    /// The model should use the `time_periods` endpoint:
+    /// ```no_run
+    /// # use asana_sdk::*;
+    /// # use asana_sdk::models::Model;
+    /// # async fn example() -> Result<(), AsanaError> {
+    /// # let mut asana = Asana::connect(String::from("1/your:personal-access-token"));
+    /// model!(TimePeriod "time_periods" {
+    ///     display_name: String,
+    ///     period: String,
+    ///     start_on: String,
+    ///     end_on: String,
+    /// });
+    ///
+    /// let periods = asana.time_periods::<TimePeriod>("12345678").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn time_periods<T: Model>(&mut self, workspace_gid: &str) -> Result<Vec<T>, AsanaError> {
+        self.param("workspace", workspace_gid).list_all::<T>().await
+    }
+
+    /// Set the time period of a goal, or clear it by passing `None`
+    ///
+    /// The model should use the `goals` endpoint:
+    /// ```
+    /// # use asana_sdk::*;
+    /// # use asana_sdk::models::Model;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// model!(Goal "goals" { name: String });
+    ///
+    /// let mut asana = Asana::builder(String::from("1/your:personal-access-token")).dry_run(true).build();
+    ///
+    /// match asana.set_goal_time_period::<Goal>("3333", Some("1111")).await {
+    ///     Err(AsanaError::DryRun(request)) => {
+    ///         assert!(request.url.starts_with("https://app.asana.com/api/1.0/goals/3333?"));
+    ///         assert_eq!(request.body, Some(serde_json::json!({ "data": { "time_period": "1111" } })));
+    ///     }
+    ///     _ => panic!("expected a dry run"),
+    /// }
+    /// # }
+    /// ```
+    pub async fn set_goal_time_period<T: Model>(&mut self, goal_gid: &str, time_period_gid: Option<&str>) -> Result<T, AsanaError> {
+        self.update::<T>(goal_gid, json!({ "time_period": time_period_gid })).await
+    }
+}
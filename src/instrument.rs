@@ -0,0 +1,46 @@
+//! Spans around API calls, with the `tracing` feature enabled
+
+use crate::AsanaError;
+use reqwest::{Method, Response};
+use std::future::Future;
+
+/// Run a call within an `asana.call` span, recording the model (when known), response status & duration
+#[cfg(feature = "tracing")]
+pub(crate) async fn call(method: &Method, path: &str, model: Option<&str>, request: impl Future<Output = Result<Response, AsanaError>>) -> Result<Response, AsanaError> {
+    use reqwest::StatusCode;
+    use tracing::Instrument;
+
+    let span = tracing::info_span!(
+        "asana.call",
+        method = %method,
+        endpoint = %path,
+        model = tracing::field::Empty,
+        status = tracing::field::Empty,
+        duration_ms = tracing::field::Empty,
+    );
+
+    if let Some(model) = model {
+        span.record("model", model);
+    }
+
+    let started = std::time::Instant::now();
+    let result = request.instrument(span.clone()).await;
+    span.record("duration_ms", started.elapsed().as_millis() as u64);
+
+    let status = match &result {
+        Ok(response) => Some(response.status()),
+        Err(AsanaError::Api { status, .. }) => Some(*status),
+        Err(AsanaError::Conflict { .. }) => Some(StatusCode::CONFLICT),
+        Err(_) => None,
+    };
+    if let Some(status) = status {
+        span.record("status", status.as_u16());
+    }
+
+    result
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) async fn call(_method: &Method, _path: &str, _model: Option<&str>, request: impl Future<Output = Result<Response, AsanaError>>) -> Result<Response, AsanaError> {
+    request.await
+}
@@ -0,0 +1,38 @@
+use crate::models::*;
+use crate::{AsanaError, Client};
+use serde_json::json;
+use std::time::Duration;
+
+impl Client {
+    /// Poll an asynchronous job until it has succeeded or failed
+    ///
+    /// The finished job is returned either way, so check its `status` for the outcome.
+    /// Every poll bypasses the client cache, so a cached unfinished job is never returned again.
+    pub async fn wait_for_job(&mut self, job_gid: &str, poll_interval: Duration) -> Result<Job, AsanaError> {
+        loop {
+            let job = self.without_cache().get::<Job>(job_gid).await?;
+            if job.is_finished() {
+                return Ok(job);
+            }
+
+            tokio::time::delay_for(poll_interval).await;
+        }
+    }
+
+    /// Start exporting all data of an organization, which Asana prepares in the background
+    pub async fn create_org_export(&mut self, organization_gid: &str) -> Result<OrganizationExport, AsanaError> {
+        self.create::<OrganizationExport>(json!({ "organization": organization_gid })).await
+    }
+
+    /// Poll an organization export until it's ready, returning its download url, or `None` if the export failed
+    pub async fn wait_for_org_export(&mut self, export_gid: &str, poll_interval: Duration) -> Result<Option<String>, AsanaError> {
+        loop {
+            let export = self.without_cache().get::<OrganizationExport>(export_gid).await?;
+            if export.is_finished() {
+                return Ok(export.into_download_url());
+            }
+
+            tokio::time::delay_for(poll_interval).await;
+        }
+    }
+}
@@ -13,13 +13,12 @@
 //!
 //! ## Sample usage
 //!
-//! ```
-//! use reqwest::{Error};
+//! ```no_run
 //! use asana_sdk::*;
 //! use asana_sdk::models::Model;
 //!
 //! #[tokio::main]
-//! async fn main() -> Result<(), Error> {
+//! async fn main() -> Result<(), AsanaError> {
 //!
 //!     // Connect with your Asana PAT (token), from https://app.asana.com/0/developer-console
 //!     let mut asana = Asana::connect(String::from("1/your:personal-access-token"));
@@ -31,8 +30,8 @@
 //!     });
 //!
 //!     // Simple calls to get one or multiple users
-//!     let mut user:  User      = asana.get::<User>("me").await;
-//!     let mut users: Vec<User> = asana.list::<User>().await;
+//!     let mut user:  User      = asana.get::<User>("me").await?;
+//!     let mut users: Vec<User> = asana.list::<User>().await?;
 //!
 //!     Ok(())
 //! }
@@ -41,13 +40,19 @@
 //! ### A few more advanced examples:
 //!
 //! Compound call to list all sections *within* a specific project
-//! ```
+//! ```no_run
+//! # use asana_sdk::*;
+//! # use asana_sdk::models::Model;
+//! # async fn example() -> Result<(), AsanaError> {
+//! # let mut asana = Asana::connect(String::from("1/your:personal-access-token"));
 //! model!(Section "sections" { name: String });
 //! model!(Project "projects" { name: String });
 //!
 //! let mut sections = asana
 //!     .from::<Project>("12345678")
-//!     .list::<Section>().await;
+//!     .list::<Section>().await?;
+//! # Ok(())
+//! # }
 //! ```
 //!
 //! A Struct for Tasks including Projects.
@@ -55,7 +60,13 @@
 //!
 //! The call will list all tasks from a specific section,
 //! and include all other projects the task is part of.
-//! ```
+//! ```no_run
+//! # use asana_sdk::*;
+//! # use asana_sdk::models::Model;
+//! # async fn example() -> Result<(), AsanaError> {
+//! # let mut asana = Asana::connect(String::from("1/your:personal-access-token"));
+//! # model!(Section "sections" {});
+//! # model!(Project "projects" { name: String });
 //! model!(TaskWithProjects "tasks" {
 //!     name: String,
 //!     projects: Vec<Project>
@@ -63,7 +74,18 @@
 //!
 //! let mut tasks_with_projects = asana
 //!      .from::<Section>("12345678")
-//!      .list::<TaskWithProjects>().await;
+//!      .list::<TaskWithProjects>().await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Fields needed on every model can be configured once on the client,
+//! and end up in each model's `extra` map.
+//! ```no_run
+//! # use asana_sdk::*;
+//! let mut asana = Asana::builder(String::from("1/your:personal-access-token"))
+//!     .opt_fields(&["permalink_url", "created_at"])
+//!     .build();
 //! ```
 //!
 //! Note that all model Structs by default include gid & resource_type,
@@ -71,6 +93,8 @@
 //!
 //! Fields which might be null in the API should be deserialized into an Option<Type>
 //! ```
+//! # use asana_sdk::*;
+//! # use asana_sdk::models::Model;
 //! model!(Assignee "assignee" {});
 //! model!(TaskWithAssignee "tasks" {
 //!     name: String,
@@ -78,79 +102,587 @@
 //! } Assignee);
 //! ```
 
-use reqwest::{Method, Response};
+use reqwest::header::HeaderMap;
+use reqwest::{Method, Request, Response, Url};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::vec::Vec;
 use log::*;
 
 pub mod models;
+pub mod de;
+pub mod prelude;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod allocations;
+mod api;
+mod builder;
+mod bulk;
+mod cache;
+mod custom_fields;
+mod error;
+mod events;
+mod export;
+mod goals;
+mod instrument;
+mod jobs;
+mod masks;
+mod pagination;
+mod presets;
+mod projects;
+mod retry;
+mod rich_text;
+mod search;
+mod tasks;
+mod time_tracking;
+mod urls;
+mod users;
+mod workspaces;
 use crate::models::*;
+use crate::cache::Cache;
+use crate::pagination::PAGE_SIZE;
+use crate::retry::RetryPolicy;
+pub use crate::allocations::Effort;
+pub use crate::api::AsanaApi;
+pub use crate::builder::ClientBuilder;
+pub use crate::bulk::MultiResult;
+pub use crate::error::AsanaError;
+pub use crate::events::{Events, EventsGap, EventsPoll};
+pub use crate::masks::MaskedGet;
+pub use crate::pagination::Pages;
+pub use crate::projects::AccessLevel;
+pub use crate::rich_text::{validate_notes, validate_rich_text};
+pub use crate::search::{similarity, SearchResult, SimilarTask};
+pub use crate::tasks::{ApprovalStatus, CreateTask, Due, InsertPosition};
+pub use crate::urls::{parse_asana_url, ResourceKind};
+pub use crate::users::CompletedFilter;
+pub use crate::workspaces::{AuditLogQuery, Workspace};
 
 pub struct Asana;
 const API_VERSION: &str = "1.0";
+/// Urls longer than this are likely to be rejected by servers & proxies along the way
+const MAX_URL_LENGTH: usize = 2048;
 
 pub struct Client {
     client: reqwest::Client,
     token: String,
     endpoint: String,
+    params: Vec<(String, String)>,
+    headers: Vec<(String, String)>,
+    opt_fields: Vec<String>,
+    next_opt_fields: Option<Vec<String>>,
+    last_headers: HeaderMap,
+    enabled_changes: Vec<String>,
+    disabled_changes: Vec<String>,
+    dry_run: bool,
+    retry: RetryPolicy,
+    lenient: bool,
+    strict: bool,
+    requested_extra: Vec<String>,
+    skipped: Vec<SkippedItem>,
+    cache: Option<Arc<Mutex<Cache>>>,
+    bypass_cache: bool,
+    traced_model: Option<&'static str>,
+    presets: HashMap<String, Vec<String>>,
+    middleware: Vec<Middleware>,
+}
+
+/// A hook which can change every request before it's sent, see `ClientBuilder::middleware()`
+pub(crate) type Middleware = Arc<dyn Fn(Request) -> Request + Send + Sync>;
+
+/// A list item which could not be deserialized, skipped by a client in lenient mode
+#[derive(Debug)]
+pub struct SkippedItem {
+    pub gid: Option<String>,
+    pub error: serde_json::Error,
+}
+
+/// A write request composed by a client in dry-run mode, which was returned instead of being sent
+#[derive(Debug, Clone, PartialEq)]
+pub struct DryRun {
+    pub method: Method,
+    pub url: String,
+    pub body: Option<Value>,
 }
 
 impl Asana {
     pub fn connect(token: String) -> Client {
-        Client {
-            token,
-            endpoint: String::from(""),
-            client: reqwest::Client::builder()
-                .user_agent("asana_sdk.rs/0.1.2")
-                .build().unwrap(),
+        Asana::builder(token).build()
+    }
+
+    /// Start configuring a client, for options beyond just the token
+    pub fn builder(token: String) -> ClientBuilder {
+        ClientBuilder::new(token)
+    }
+
+    /// Connect with a personal access token from the `ASANA_ACCESS_TOKEN` environment variable
+    ///
+    /// ```
+    /// # use asana_sdk::{Asana, AsanaError};
+    /// std::env::set_var("ASANA_ACCESS_TOKEN", "1/1234567890:abcdef\n");
+    /// assert!(Asana::from_env().is_ok());
+    ///
+    /// std::env::set_var("ASANA_ACCESS_TOKEN", "my token");
+    /// assert!(matches!(Asana::from_env(), Err(AsanaError::Validation(_))));
+    ///
+    /// std::env::remove_var("ASANA_ACCESS_TOKEN");
+    /// assert!(matches!(Asana::from_env(), Err(AsanaError::Validation(_))));
+    /// ```
+    pub fn from_env() -> Result<Client, AsanaError> {
+        Asana::from_env_var("ASANA_ACCESS_TOKEN")
+    }
+
+    /// Connect with a token from another environment variable, see [`Asana::from_env`]
+    pub fn from_env_var(var: &str) -> Result<Client, AsanaError> {
+        let token = std::env::var(var).map_err(|error| match error {
+            std::env::VarError::NotPresent => AsanaError::Validation(format!("Environment variable {} is not set", var)),
+            std::env::VarError::NotUnicode(_) => AsanaError::Validation(format!("Environment variable {} is not valid unicode", var)),
+        })?;
+
+        // Surrounding whitespace usually comes from a trailing newline in an env file, but anything else
+        // would never authenticate and couldn't even be sent as a header
+        let token = token.trim();
+        if token.is_empty() {
+            return Err(AsanaError::Validation(format!("Environment variable {} is empty", var)));
+        }
+        if !token.chars().all(|c| c.is_ascii_graphic()) {
+            return Err(AsanaError::Validation(format!("Environment variable {} does not contain a valid access token", var)));
         }
 
+        Ok(Asana::connect(token.to_string()))
     }
 }
 
 impl Client {
-    pub async fn get<T: Model>(&mut self, gid: &str) -> T {
-        let model: Wrapper<T> = self
-            .call::<T>(Method::GET, Some(gid)).await
-            .json().await.unwrap();
+    pub async fn get<T: Model>(&mut self, gid: &str) -> Result<T, AsanaError> {
+        let bypass = std::mem::take(&mut self.bypass_cache);
+        if let Some(cache) = self.cache.clone().filter(|_| self.endpoint.is_empty() && self.params.is_empty()) {
+            return self.get_cached::<T>(&cache, gid, !bypass).await;
+        }
+
+        let response = self.call::<T>(Method::GET, Some(gid), None).await?;
+        let model: Wrapper<T> = parse(response).await?;
+        self.check_strict(&model.data)?;
+
+        Ok(model.data)
+    }
+
+    /// Fetch an entity with the model's `opt_fields`, and report which requested fields are missing from the response
+    ///
+    /// Asana leaves out fields it doesn't know (rather than failing), so a misspelled field just never shows up.
+    /// Fields which are present but `null` aren't reported.
+    pub async fn validate_fields<T: Model>(&mut self, gid: &str) -> Result<Vec<String>, AsanaError> {
+        let response = self.call::<T>(Method::GET, Some(gid), None).await?;
+        let entity: Wrapper<HashMap<String, Value>> = parse(response).await?;
+
+        Ok(T::field_names().iter()
+            .filter(|field| !entity.data.contains_key(**field))
+            .map(|field| field.to_string())
+            .collect())
+    }
+
+    /// Create an entity, sending the given fields as the `data` body of a `POST` request
+    ///
+    /// Like `list()`, this can be scoped to a relational endpoint with `from()`, to create nested entities.
+    /// For example subtasks, which are created through `/tasks/{gid}/subtasks`:
+    /// ```
+    /// # use asana_sdk::*;
+    /// # use asana_sdk::models::Model;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// model!(Task "tasks" {});
+    /// model!(Subtask "subtasks" { name: String });
+    ///
+    /// let mut asana = Asana::builder(String::from("1/your:personal-access-token")).dry_run(true).build();
+    /// let subtask = asana
+    ///     .from::<Task>("12345678")
+    ///     .create::<Subtask>(serde_json::json!({ "name": "Write docs" })).await;
+    ///
+    /// match subtask {
+    ///     Err(AsanaError::DryRun(request)) => assert!(request.url.starts_with("https://app.asana.com/api/1.0/tasks/12345678/subtasks/")),
+    ///     _ => panic!("expected a dry run"),
+    /// }
+    /// # }
+    /// ```
+    pub async fn create<T: Model>(&mut self, data: Value) -> Result<T, AsanaError> {
+        let path = format!("{}{}/", self.endpoint, T::endpoint());
+        self.endpoint.clear();
+
+        self.create_at::<T>(&path, data).await
+    }
+
+    /// Create an entity at an explicit path, for endpoints which don't follow the model's endpoint
+    pub(crate) async fn create_at<T: Model>(&mut self, path: &str, data: Value) -> Result<T, AsanaError> {
+        let response = self.call_at::<T>(Method::POST, path, Some(with_default_subtype::<T>(data))).await?;
+        let model: Wrapper<T> = parse(response).await?;
+        self.check_strict(&model.data)?;
 
-        model.data
+        Ok(model.data)
     }
 
-    pub async fn list<T: Model>(&mut self) -> Vec<T> {
-        let model: ListWrapper<T> =  self
-            .call::<T>(Method::GET, None).await
-            .json().await.unwrap();
+    /// Update an entity by gid, sending the given fields as the `data` body of a `PUT` request
+    ///
+    /// Like reads, writes request the model's `opt_fields`, so the returned entity has all of its declared fields.
+    /// ```
+    /// # use asana_sdk::*;
+    /// # use asana_sdk::models::Model;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// model!(Task "tasks" { name: String, completed: bool });
+    ///
+    /// let mut asana = Asana::builder(String::from("1/your:personal-access-token")).dry_run(true).build();
+    /// let task = asana.update::<Task>("12345678", serde_json::json!({ "completed": true })).await;
+    ///
+    /// match task {
+    ///     Err(AsanaError::DryRun(request)) => assert!(request.url.ends_with("?opt_fields=this.%28resource_type%7Cname%7Ccompleted%29")),
+    ///     _ => panic!("expected a dry run"),
+    /// }
+    /// # }
+    /// ```
+    pub async fn update<T: Model>(&mut self, gid: &str, data: Value) -> Result<T, AsanaError> {
+        let response = self.call::<T>(Method::PUT, Some(gid), Some(data)).await;
+        self.invalidate_cached::<T>(gid);
+        let response = response?;
+        let model: Wrapper<T> = parse(response).await?;
+        self.check_strict(&model.data)?;
 
+        Ok(model.data)
+    }
+
+    /// Delete an entity by gid
+    ///
+    /// The response body isn't parsed, so both an empty `data` object and `204 No Content` are fine.
+    pub async fn delete<T: Model>(&mut self, gid: &str) -> Result<(), AsanaError> {
+        let response = self.call::<T>(Method::DELETE, Some(gid), None).await;
+        self.invalidate_cached::<T>(gid);
+
+        discard(response?).await
+    }
+
+    pub async fn list<T: Model>(&mut self) -> Result<Vec<T>, AsanaError> {
+        let (data, _) = self.list_checked::<T>().await?;
+
+        Ok(data)
+    }
+
+    /// List entities, together with a flag indicating whether Asana has more pages available
+    ///
+    /// `list()` only returns the first page, so a `true` flag means the result was truncated.
+    pub async fn list_checked<T: Model>(&mut self) -> Result<(Vec<T>, bool), AsanaError> {
+        self.skipped.clear();
+        // Asana only pages (and reports a next page) when a limit is sent
+        if !self.params.iter().any(|(key, _)| key == "limit") {
+            self.param("limit", PAGE_SIZE);
+        }
+        let response = self.call::<T>(Method::GET, None, None).await;
         self.endpoint.clear();
 
-        model.data
+        let model: ListWrapper<T> = self.parse_list(response?).await?;
+
+        Ok((model.data, model.next_page.is_some()))
     }
 
     pub fn from<T: Model>(&mut self, relational_gid: &str) -> &mut Client {
-        self.endpoint = format!("{}/{}/", T::endpoint(), relational_gid);
+        self.scope(&T::endpoint(), relational_gid)
+    }
+
+    fn scope(&mut self, endpoint: &str, relational_gid: &str) -> &mut Client {
+        self.endpoint = format!("{}/{}/", endpoint, relational_gid);
+        self
+    }
+
+    /// The headers of the most recent response, e.g. to inspect `Asana-Change` deprecation notices
+    pub fn last_headers(&self) -> &HeaderMap {
+        &self.last_headers
+    }
+
+    /// Items skipped by the most recent list call, because they could not be deserialized in lenient mode
+    pub fn skipped_items(&self) -> &[SkippedItem] {
+        &self.skipped
+    }
+
+    /// Replace the builder's extra `opt_fields` for the next call only
+    pub fn with_opt_fields(&mut self, fields: &[&str]) -> &mut Client {
+        self.next_opt_fields = Some(fields.iter().map(|f| f.to_string()).collect());
+        self
+    }
+
+    /// Send an extra header with the next call only, e.g. to try out a beta feature
+    pub fn with_header(&mut self, name: &str, value: &str) -> &mut Client {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Send an idempotency key with the next call only, so retrying a `create()` after a timeout can't create a duplicate
+    ///
+    /// Automatic retries of the call reuse the same key, and only writes with a key are retried after a server error.
+    /// Use a fresh key for each distinct entity.
+    /// ```no_run
+    /// # use asana_sdk::*;
+    /// # use asana_sdk::models::Model;
+    /// # async fn example() -> Result<(), AsanaError> {
+    /// # let mut asana = Asana::connect(String::from("1/your:personal-access-token"));
+    /// model!(Task "tasks" { name: String });
+    ///
+    /// let task = asana
+    ///     .with_idempotency_key("import-row-1234")
+    ///     .create::<Task>(serde_json::json!({ "name": "Imported task", "workspace": "12345678" })).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_idempotency_key(&mut self, key: &str) -> &mut Client {
+        self.with_header("X-Idempotency-Key", key)
+    }
+
+    /// A client with the same configuration & connection pool, but its own call state, for concurrent calls
+    fn fork(&self) -> Client {
+        Client {
+            client: self.client.clone(),
+            token: self.token.clone(),
+            endpoint: String::from(""),
+            params: Vec::new(),
+            headers: Vec::new(),
+            opt_fields: self.opt_fields.clone(),
+            next_opt_fields: None,
+            last_headers: HeaderMap::new(),
+            enabled_changes: self.enabled_changes.clone(),
+            disabled_changes: self.disabled_changes.clone(),
+            dry_run: self.dry_run,
+            retry: self.retry.clone(),
+            lenient: self.lenient,
+            strict: self.strict,
+            requested_extra: Vec::new(),
+            skipped: Vec::new(),
+            cache: self.cache.clone(),
+            bypass_cache: false,
+            traced_model: None,
+            presets: self.presets.clone(),
+            middleware: self.middleware.clone(),
+        }
+    }
+
+    fn param(&mut self, key: &str, value: &str) -> &mut Client {
+        self.params.push((key.to_string(), value.to_string()));
         self
     }
 
-    async fn call<T: Model>(&mut self, method: Method, gid: Option<&str>) -> Response {
+    async fn call<T: Model>(&mut self, method: Method, gid: Option<&str>, body: Option<Value>) -> Result<Response, AsanaError> {
         // Add both relational and main endpoints, and entity gid if supplied
-        let url = format!("{}{}/", self.endpoint, T::endpoint());
-        let url = format!("{}{}", url, match gid {
-            Some(gid) => format!("{}", gid),
-            None => "".to_string()
-        });
+        let path = format!("{}{}/{}", self.endpoint, T::endpoint(), gid.unwrap_or(""));
 
         // Clear relational endpoint state from client
         self.endpoint.clear();
 
-        // Add relational & root field inclusions as query parameters
-        let opts = format!("this.({}),{}", T::field_names().join("|"), T::opt_strings().join(","));
-        let url = format!("{}?opt_fields={}", url, opts);
+        self.call_at::<T>(method, &path, body).await
+    }
+
+    /// Call an explicit path, requesting the model's `opt_fields`
+    async fn call_at<T: Model>(&mut self, method: Method, path: &str, body: Option<Value>) -> Result<Response, AsanaError> {
+        let extra = self.next_opt_fields.take().unwrap_or_else(|| self.opt_fields.clone());
+        let opts = opt_fields::<T>(&extra);
+        self.requested_extra = extra;
+        self.traced_model = Some(std::any::type_name::<T>());
+
+        self.request(method, path, Some(opts), body).await
+    }
+
+    /// In strict mode, reject models with fields which were neither declared nor requested as extra `opt_fields`
+    fn check_strict<T: Model>(&self, model: &T) -> Result<(), serde_json::Error> {
+        if !self.strict {
+            return Ok(());
+        }
+
+        let unexpected: Vec<&str> = model.extra_keys().into_iter()
+            .filter(|key| !self.requested_extra.iter().any(|field| field == key))
+            .collect();
+
+        if unexpected.is_empty() {
+            Ok(())
+        } else {
+            Err(serde::de::Error::custom(format!("unexpected fields on {} {}: {}", T::endpoint(), model.gid(), unexpected.join(", "))))
+        }
+    }
+
+    /// Parse a page of entities, which in lenient mode skips the items which fail to deserialize (or fail the strict check)
+    async fn parse_list<T: Model>(&mut self, response: Response) -> Result<ListWrapper<T>, AsanaError> {
+        if !self.lenient {
+            let page: ListWrapper<T> = parse(response).await?;
+            for item in &page.data {
+                self.check_strict(item)?;
+            }
+            return Ok(page);
+        }
+
+        let page: ListWrapper<Value> = parse(response).await?;
+        let mut data = Vec::with_capacity(page.data.len());
+
+        for item in page.data {
+            let gid = item.get("gid").and_then(Value::as_str).map(String::from);
+            match serde_json::from_value::<T>(item).and_then(|item| self.check_strict(&item).map(|_| item)) {
+                Ok(item) => data.push(item),
+                Err(error) => {
+                    warn!("Skipping {} {:?}: {}", T::endpoint(), gid, error);
+                    self.skipped.push(SkippedItem { gid, error });
+                }
+            }
+        }
+
+        Ok(ListWrapper { data, next_page: page.next_page })
+    }
+
+    /// Fetch a single relation of an entity, including only the given fields of the related entities
+    async fn relation<R: DeserializeOwned>(&mut self, endpoint: &str, gid: &str, relation: &str, fields: &[&str]) -> Result<R, AsanaError> {
+        let path = format!("{}/{}", endpoint, gid);
+        let opts = format!("{}.({})", relation, fields.join("|"));
+        let response = self.request(Method::GET, &path, Some(opts), None).await?;
+
+        let mut entity: Wrapper<HashMap<String, Value>> = parse(response).await?;
+        let related = entity.data.remove(relation).unwrap_or(Value::Null);
+
+        Ok(serde_json::from_value(related)?)
+    }
+
+    /// Post to an action endpoint like `/tasks/{gid}/addProject`
+    ///
+    /// Actions respond with an empty `data` object, an empty body, or `204 No Content`, so the body is never parsed.
+    async fn action(&mut self, endpoint: &str, gid: &str, action: &str, data: Value) -> Result<(), AsanaError> {
+        let path = format!("{}/{}/{}", endpoint, gid, action);
+        let response = self.request(Method::POST, &path, None, Some(data)).await?;
+
+        discard(response).await
+    }
 
-        let request_url = format!("https://app.asana.com/api/{}/{}", API_VERSION, url);
-        info!("{}", request_url);
+    /// Send a request, within an `asana.call` span when the `tracing` feature is enabled
+    async fn request(&mut self, method: Method, path: &str, opt_fields: Option<String>, body: Option<Value>) -> Result<Response, AsanaError> {
+        let model = self.traced_model.take();
+        let traced = method.clone();
 
-        self.client.request(method, &request_url)
-            .header("Authorization", format!("Bearer {}", &self.token))
-            .send().await.unwrap()
+        instrument::call(&traced, path, model, self.send(method, path, opt_fields, body)).await
     }
-}
\ No newline at end of file
+
+    async fn send(&mut self, method: Method, path: &str, opt_fields: Option<String>, body: Option<Value>) -> Result<Response, AsanaError> {
+        let mut query = std::mem::take(&mut self.params);
+        if let Some(opts) = opt_fields {
+            query.insert(0, (String::from("opt_fields"), opts));
+        }
+
+        let request_url = format!("https://app.asana.com/api/{}/{}", API_VERSION, path);
+        let mut url = Url::parse(&request_url).expect("API urls are valid");
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(&query);
+        }
+
+        // Reads with very long urls (large models) are sent as a POST, with the query in the body instead
+        let mut request = if method == Method::GET && url.as_str().len() > MAX_URL_LENGTH {
+            info!("GET {} (as POST, url too long)", request_url);
+            self.client.request(Method::POST, &request_url)
+                .header("X-HTTP-Method-Override", "GET")
+                .form(&query)
+        } else {
+            info!("{}", url);
+            self.client.request(method.clone(), url)
+        };
+
+        request = request.header("Authorization", format!("Bearer {}", &self.token));
+        for (name, value) in std::mem::take(&mut self.headers) {
+            request = request.header(name.as_str(), value.as_str());
+        }
+
+        if !self.enabled_changes.is_empty() {
+            request = request.header("Asana-Enable", self.enabled_changes.join(","));
+        }
+        if !self.disabled_changes.is_empty() {
+            request = request.header("Asana-Disable", self.disabled_changes.join(","));
+        }
+
+        if let Some(data) = &body {
+            validate_notes(data)?;
+        }
+
+        // Write requests wrap their fields in a `data` envelope, like responses do
+        let body = body.map(|data| serde_json::json!({ "data": data }));
+        let request = match &body {
+            Some(body) => request.json(body),
+            None => request
+        };
+
+        let mut request = request.build()?;
+        for middleware in &self.middleware {
+            request = middleware(request);
+        }
+
+        // In dry-run mode, writes are handed back to the caller instead of being sent
+        if self.dry_run && method != Method::GET {
+            return Err(AsanaError::DryRun(DryRun {
+                method,
+                url: request.url().to_string(),
+                body,
+            }));
+        }
+
+        // Retry rate limits & server errors, cloning the request since sending consumes it
+        let replayable = method == Method::GET || request.headers().contains_key("X-Idempotency-Key");
+        let mut attempt = 0;
+        let response = loop {
+            let response = self.client.execute(request.try_clone().expect("JSON requests can be cloned")).await?;
+            match self.retry.delay(attempt, &response, replayable) {
+                Some(delay) => {
+                    warn!("Asana returned {}, retrying in {:?}", response.status(), delay);
+                    tokio::time::delay_for(delay).await;
+                    attempt += 1;
+                }
+                None => break response,
+            }
+        };
+
+        self.last_headers = response.headers().clone();
+
+        for change in self.last_headers.get_all("asana-change") {
+            warn!("Asana-Change: {}", change.to_str().unwrap_or_default());
+        }
+
+        if !response.status().is_success() {
+            return Err(AsanaError::from_response(response).await);
+        }
+
+        Ok(response)
+    }
+}
+
+// Add relational & root field inclusions as query parameters
+fn opt_fields<T: Model>(extra: &[String]) -> String {
+    let fields = dedup(T::field_names().iter().copied().chain(extra.iter().map(|f| f.as_str())));
+    let includes = T::opt_strings();
+    let includes = dedup(includes.iter().map(|i| i.as_str()));
+
+    let this = format!("this.({})", fields.join("|"));
+    std::iter::once(this.as_str()).chain(includes).collect::<Vec<_>>().join(",")
+}
+
+// Drop repeated entries, keeping the first occurrence of each
+fn dedup<'a>(items: impl Iterator<Item = &'a str>) -> Vec<&'a str> {
+    let mut seen = std::collections::HashSet::new();
+    items.filter(|item| seen.insert(*item)).collect()
+}
+
+// Read a response to the end without parsing it, which also frees the connection for reuse
+async fn discard(response: Response) -> Result<(), AsanaError> {
+    response.bytes().await?;
+    Ok(())
+}
+
+/// Add the model's default `resource_subtype` to a create body, unless it sets one itself
+pub(crate) fn with_default_subtype<T: Model>(mut data: Value) -> Value {
+    if let (Some(subtype), Value::Object(fields)) = (T::default_subtype(), &mut data) {
+        fields.entry("resource_subtype").or_insert_with(|| Value::from(subtype));
+    }
+
+    data
+}
+
+async fn parse<D: DeserializeOwned>(response: Response) -> Result<D, AsanaError> {
+    let body = response.text().await?;
+    serde_json::from_str(&body).map_err(|error| AsanaError::Deserialize { error, body: Some(body) })
+}
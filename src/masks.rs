@@ -0,0 +1,104 @@
+use crate::models::*;
+use crate::{dedup, parse, AsanaError, Client};
+use reqwest::Method;
+use std::marker::PhantomData;
+
+/// A single get with its fields picked at call time, created with `get_with_masks()`
+pub struct MaskedGet<'a, T> {
+    client: &'a mut Client,
+    gid: String,
+    fields: Option<Vec<String>>,
+    includes: Vec<(String, Vec<String>)>,
+    model: PhantomData<T>,
+}
+
+impl<'a, T: Model> MaskedGet<'a, T> {
+    /// Request these top-level fields instead of the model's declared fields
+    ///
+    /// This allows minimal fetches for hot paths, even with a model which declares no fields at all.
+    /// Fields the model doesn't declare end up in its `extra` map:
+    /// ```no_run
+    /// # use asana_sdk::*;
+    /// # use asana_sdk::models::Model;
+    /// # async fn example() -> Result<(), AsanaError> {
+    /// # let mut asana = Asana::connect(String::from("1/your:personal-access-token"));
+    /// model!(Task "tasks" {});
+    ///
+    /// let task = asana.get_with_masks::<Task>("12345678")
+    ///     .fields(&["name", "num_subtasks"])
+    ///     .send().await?;
+    /// println!("{} has {} subtasks", task.extra()["name"], task.extra()["num_subtasks"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fields(mut self, fields: &[&str]) -> MaskedGet<'a, T> {
+        self.fields = Some(fields.iter().map(|f| f.to_string()).collect());
+        self
+    }
+
+    /// Request these fields of a related entity, e.g. `include("projects", &["name"])`
+    ///
+    /// Once any include is given, the model's own includes are no longer requested.
+    pub fn include(mut self, relation: &str, fields: &[&str]) -> MaskedGet<'a, T> {
+        self.includes.push((relation.to_string(), fields.iter().map(|f| f.to_string()).collect()));
+        self
+    }
+
+    pub async fn send(self) -> Result<T, AsanaError> {
+        let fields = match &self.fields {
+            Some(fields) => dedup(std::iter::once("resource_type").chain(fields.iter().map(|f| f.as_str()))),
+            None => T::field_names().to_vec(),
+        };
+
+        let mut opts = vec![format!("this.({})", fields.join("|"))];
+        if self.includes.is_empty() {
+            opts.extend(T::opt_strings());
+        } else {
+            for (relation, fields) in &self.includes {
+                let fields = dedup(["gid", "resource_type"].iter().copied().chain(fields.iter().map(|f| f.as_str())));
+                opts.push(format!("{}.({})", relation, fields.join("|")));
+            }
+        }
+
+        let path = format!("{}/{}", T::endpoint(), self.gid);
+        self.client.endpoint.clear();
+        self.client.next_opt_fields = None;
+        self.client.requested_extra = self.fields.unwrap_or_default();
+
+        let response = self.client.request(Method::GET, &path, Some(opts.join(",")), None).await?;
+        let model: Wrapper<T> = parse(response).await?;
+        self.client.check_strict(&model.data)?;
+
+        Ok(model.data)
+    }
+}
+
+impl Client {
+    /// Get an entity with its top-level & included fields picked for this call, rather than by the model
+    ///
+    /// Fields beyond the ones the model declares end up in its `extra` map.
+    /// ```no_run
+    /// # use asana_sdk::*;
+    /// # use asana_sdk::models::Model;
+    /// # async fn example() -> Result<(), AsanaError> {
+    /// # let mut asana = Asana::connect(String::from("1/your:personal-access-token"));
+    /// model!(Project "projects" { name: String });
+    /// model!(Task "tasks" { name: String, projects: Vec<Project> } Project);
+    ///
+    /// let task = asana.get_with_masks::<Task>("12345678")
+    ///     .fields(&["name", "projects", "due_on"])
+    ///     .include("projects", &["name", "color"])
+    ///     .send().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_with_masks<T: Model>(&mut self, gid: &str) -> MaskedGet<'_, T> {
+        MaskedGet {
+            client: self,
+            gid: gid.to_string(),
+            fields: None,
+            includes: Vec::new(),
+            model: PhantomData,
+        }
+    }
+}
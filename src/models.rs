@@ -1,5 +1,6 @@
-use serde::{Deserialize};
+use serde::{Deserialize, Serialize};
 use serde::de::DeserializeOwned;
+use std::collections::HashMap;
 
 /// A macro for generating Asana model structs
 ///
@@ -10,9 +11,17 @@ use serde::de::DeserializeOwned;
 /// * It will include & deserialize the `email` & `name` fields as Strings.
 /// * The fields `gid` and `resource_type` are included by default, and don't have to be specified.
 ///
-/// Any extra fields returned from the Asana API are flattened by [`Serde`] into a [`Hashmap`]:
+/// Any extra fields returned from the Asana API are flattened by [`Serde`] into a [`Hashmap`].
+///
+/// Fields are private, and can be read through accessor methods with the same name (`user.email()`, `user.extra()`).
+/// The `gid` is always available through the [`Model`] trait.
+/// With the `public-fields` feature enabled, all fields are `pub` as well, and the accessors are still generated,
+/// so code using them keeps compiling when another crate in the dependency graph enables the feature.
+///
+/// Models derive `Clone` and `PartialEq` (besides `Debug` and serde's traits), so field types need to implement them as well.
 ///
 /// ```
+/// # use asana_sdk::model;
 /// use asana_sdk::models::Model;
 ///
 /// model!(User "users" {
@@ -27,6 +36,8 @@ use serde::de::DeserializeOwned;
 /// This example is suitable for getting a set of Tasks, with the set of Projects that tasks belongs to, and the Assignee the task is assigned to.
 /// Of course, you can include more fields than just `name` on the includes.
 /// ```
+/// # use asana_sdk::model;
+/// # use asana_sdk::models::Model;
 /// model!(Assignee "assignee" {
 ///     name: String
 /// });
@@ -37,43 +48,566 @@ use serde::de::DeserializeOwned;
 ///
 /// model!(Tasks "tasks" {
 ///     name: String,
-///     assignee: Option<Assignee>
+///     assignee: Option<Assignee>,
 ///     projects: Vec<Project>
 /// } Project, Assignee);
 /// ```
+///
+/// Included models are full models themselves, so fields they don't declare end up in their own `extra` map:
+/// ```
+/// # use asana_sdk::model;
+/// use asana_sdk::models::Model;
+///
+/// model!(Project "projects" { name: String });
+/// model!(Task "tasks" { projects: Vec<Project> } Project);
+///
+/// let task = Task::from_value(serde_json::json!({
+///     "gid": "1", "resource_type": "task",
+///     "projects": [{ "gid": "2", "resource_type": "project", "name": "Roadmap", "color": "light-green" }]
+/// })).unwrap();
+///
+/// assert_eq!(task.projects()[0].extra()["color"], "light-green");
+/// assert_eq!(task.clone(), task);
+/// ```
+///
+/// Every include requests its `gid` and `resource_type`, even when the included model declares no fields of its own:
+/// ```
+/// # use asana_sdk::model;
+/// use asana_sdk::models::Model;
+///
+/// model!(Project "projects" {});
+/// model!(Task "tasks" { projects: Vec<Project> } Project);
+///
+/// assert_eq!(Task::opt_strings(), vec!["projects.(gid|resource_type)"]);
+/// ```
+///
+/// Fields computed by Asana can be marked `#[readonly]`, so they are never sent when a model is serialized for a write.
+/// Other attributes, like `#[serde(rename = "...")]`, are passed through to the field as-is.
+/// ```
+/// # use asana_sdk::model;
+/// # use asana_sdk::models::Model;
+/// model!(Task "tasks" {
+///     name: String,
+///     #[readonly]
+///     created_at: String,
+/// });
+///
+/// let task: Task = serde_json::from_str(r#"{
+///     "gid": "1", "resource_type": "task", "name": "Write docs", "created_at": "2020-10-01T12:00:00.000Z"
+/// }"#).unwrap();
+///
+/// assert!(serde_json::to_value(&task).unwrap().get("created_at").is_none());
+/// ```
+///
+/// Common task fields can be added with the `..TaskFields` preset, which declares
+/// `completed: bool`, `completed_at: Option<String>` and `completed_by: Option<Compact>`,
+/// the `start_on` & `start_at` timeline fields as `Option<String>`, and the computed counts `num_subtasks` & `num_likes`
+/// as `Option<Count>` (a [`de::Count`](crate::de::Count), read from numbers or strings) with `liked: bool`.
+/// ```
+/// # use asana_sdk::model;
+/// # use asana_sdk::models::Model;
+/// model!(Task "tasks" {
+///     name: String,
+///     ..TaskFields
+/// });
+///
+/// let task = Task::from_value(serde_json::json!({
+///     "gid": "1", "resource_type": "task", "name": "Write docs",
+///     "completed": true, "completed_at": "2020-10-01T12:00:00.000Z", "completed_by": { "gid": "2" },
+///     "num_subtasks": "3"
+/// })).unwrap();
+///
+/// assert_eq!(task.completed_by().as_ref().unwrap().gid, "2");
+/// assert_eq!(task.num_subtasks(), &Some(asana_sdk::de::Count(3)));
+/// assert_eq!(task.num_likes(), &None);
+/// ```
+///
+/// Likewise, the `..CustomFields` preset declares `custom_fields: Vec<CustomFieldValue>` with its include,
+/// and implements [`CustomFields`] for typed access to the values.
+/// ```
+/// # use asana_sdk::model;
+/// use asana_sdk::models::{CustomFields, Model};
+///
+/// model!(Task "tasks" {
+///     name: String,
+///     ..CustomFields
+/// });
+///
+/// let task = Task::from_value(serde_json::json!({
+///     "gid": "1", "resource_type": "task", "name": "Fix login",
+///     "custom_fields": [{
+///         "gid": "2", "resource_type": "custom_field", "name": "Priority", "resource_subtype": "enum",
+///         "enum_value": { "gid": "3", "name": "High", "color": "red", "enabled": true }
+///     }, {
+///         "gid": "4", "resource_type": "custom_field", "name": "Estimate", "resource_subtype": "number",
+///         "number_value": "1.50"
+///     }]
+/// })).unwrap();
+///
+/// assert_eq!(task.custom_field_enum("2").unwrap().name, "High");
+/// assert!(task.custom_field_enum("4").is_none());
+/// assert_eq!(task.custom_fields().len(), 2);
+/// ```
+///
+/// Expensive fields can be marked `#[lazy]`, so they are not requested by default. They are still deserialized
+/// (or left at their default) when requested for a single call, e.g. `asana.with_opt_fields(&["html_notes"]).get::<Task>(gid)`.
+/// ```
+/// # use asana_sdk::model;
+/// use asana_sdk::models::Model;
+///
+/// model!(Task "tasks" {
+///     name: String,
+///     #[lazy]
+///     html_notes: Option<String>,
+/// });
+///
+/// assert_eq!(Task::field_names(), &["resource_type", "name"]);
+/// ```
+///
+/// Models for a subtype of a resource, like milestones or approvals among tasks, can declare that subtype after
+/// the endpoint. It's then added as the `resource_subtype` of every create, unless the body already sets one.
+/// ```
+/// # use asana_sdk::*;
+/// use asana_sdk::models::Model;
+/// # #[tokio::main]
+/// # async fn main() {
+///
+/// model!(Milestone "tasks" as "milestone" {
+///     name: String,
+/// });
+///
+/// let mut asana = Asana::builder(String::from("1/your:personal-access-token")).dry_run(true).build();
+///
+/// match asana.create::<Milestone>(serde_json::json!({ "name": "Launch" })).await {
+///     Err(AsanaError::DryRun(request)) => assert_eq!(
+///         request.body,
+///         Some(serde_json::json!({ "data": { "name": "Launch", "resource_subtype": "milestone" } }))
+///     ),
+///     _ => panic!("expected a dry run"),
+/// }
+/// # }
+/// ```
 #[macro_export]
 macro_rules! model {
-    ($name:ident $endpoint:literal { $( $field:ident: $fty:ty ),* $(,)? } $( $include:ident),* $(,)? ) => {
-        #[derive(serde::Serialize, serde::Deserialize, Debug)]
-        pub struct $name {
-            gid: String,
-            resource_type: String,
-            $( $field: $fty, )*
-            #[serde(flatten)]
-            extra: std::collections::HashMap<String, serde_json::Value>,
+    // Munch field markers & declarations one at a time, collecting each field with its serde attributes,
+    // and the names of the fields which are requested by default
+    (@fields [$name:ident $endpoint:literal [$($include:path),*] $subtype:tt] $out:tt $names:tt [] [] ..CustomFields $(, $($rest:tt)*)?) => {
+        impl $crate::models::CustomFields for $name {
+            fn custom_fields(&self) -> &[$crate::models::CustomFieldValue] { &self.custom_fields }
+        }
+
+        $crate::model!(@fields [$name $endpoint [$crate::models::CustomFieldValue $(, $include)*] $subtype] $out $names [] []
+            #[serde(default)] custom_fields: Vec<$crate::models::CustomFieldValue>
+            $(, $($rest)*)?);
+    };
+    (@fields $meta:tt $out:tt $names:tt [] [] ..TaskFields $(, $($rest:tt)*)?) => {
+        $crate::model!(@fields $meta $out $names [] []
+            #[serde(default)] completed: bool,
+            #[serde(default)] #[readonly] completed_at: Option<String>,
+            #[serde(default)] #[readonly] completed_by: Option<$crate::models::Compact>,
+            #[serde(default)] start_on: Option<String>,
+            #[serde(default)] start_at: Option<String>,
+            #[serde(default)] #[readonly] num_subtasks: Option<$crate::de::Count>,
+            #[serde(default)] #[readonly] num_likes: Option<$crate::de::Count>,
+            #[serde(default)] liked: bool
+            $(, $($rest)*)?);
+    };
+    (@fields $meta:tt $out:tt $names:tt [$($attrs:tt)*] $lazy:tt #[readonly] $($rest:tt)*) => {
+        $crate::model!(@fields $meta $out $names [$($attrs)* #[serde(skip_serializing)]] $lazy $($rest)*);
+    };
+    (@fields $meta:tt $out:tt $names:tt [$($attrs:tt)*] $lazy:tt #[lazy] $($rest:tt)*) => {
+        $crate::model!(@fields $meta $out $names [$($attrs)* #[serde(default)]] [lazy] $($rest)*);
+    };
+    (@fields $meta:tt $out:tt $names:tt [$($attrs:tt)*] $lazy:tt #[$attr:meta] $($rest:tt)*) => {
+        $crate::model!(@fields $meta $out $names [$($attrs)* #[$attr]] $lazy $($rest)*);
+    };
+    (@fields $meta:tt [$($out:tt)*] $names:tt [$($attrs:tt)*] [lazy] $field:ident: $fty:ty $(, $($rest:tt)*)?) => {
+        $crate::model!(@fields $meta [$($out)* { $($attrs)* } $field: $fty,] $names [] [] $($($rest)*)?);
+    };
+    (@fields $meta:tt [$($out:tt)*] [$($names:ident)*] [$($attrs:tt)*] [] $field:ident: $fty:ty $(, $($rest:tt)*)?) => {
+        $crate::model!(@fields $meta [$($out)* { $($attrs)* } $field: $fty,] [$($names)* $field] [] [] $($($rest)*)?);
+    };
+    (@fields [$name:ident $endpoint:literal [$($include:path),*] [$($subtype:literal)?]] [$( { $($attr:tt)* } $field:ident: $fty:ty, )*] [$($requested:ident)*] [] []) => {
+        $crate::__model_struct!($name [$( { $($attr)* } $field: $fty, )*]);
+
+        impl $name {
+            /// The declared fields & `extra` fields which differ between two states of this entity
+            pub fn diff(&self, other: &Self) -> Vec<$crate::models::FieldChange> {
+                let mut changes = Vec::new();
+                $(
+                    if self.$field != other.$field {
+                        changes.push($crate::models::FieldChange::new(stringify!($field), &self.$field, &other.$field));
+                    }
+                )*
+                $crate::models::FieldChange::diff_extra(&self.extra, &other.extra, &mut changes);
+
+                changes
+            }
         }
 
         impl Model for $name {
             fn endpoint() -> String { $endpoint.to_string() }
 
+            fn gid(&self) -> &str { &self.gid }
+
             fn opt_strings() -> Vec<String> {
-                vec![$(format!("{}.({})", $include::endpoint(), $include::field_names().join("|"))),*]
+                vec![$(format!("{}.(gid|{})", <$include as Model>::endpoint(), <$include as Model>::field_names().join("|"))),*]
             }
 
             fn field_names() -> &'static [&'static str] {
-                &["resource_type", $(stringify!($field)),*]
+                &["resource_type", $(stringify!($requested)),*]
+            }
+
+            fn extra_keys(&self) -> Vec<&str> {
+                self.extra.keys().map(String::as_str).collect()
+            }
+
+            fn default_subtype() -> Option<&'static str> {
+                None $(.or(Some($subtype)))?
             }
         }
     };
+    ($name:ident $endpoint:literal $(as $subtype:literal)? { $($fields:tt)* } $( $include:path),* $(,)? ) => {
+        $crate::model!(@fields [$name $endpoint [$($include),*] [$($subtype)?]] [] [] [] [] $($fields)*);
+    };
+}
+
+/// Emits a model struct with private fields, and an accessor method for each field
+#[cfg(not(feature = "public-fields"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __model_struct {
+    ($name:ident [$( { $($attr:tt)* } $field:ident: $fty:ty, )*]) => {
+        #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+        pub struct $name {
+            gid: String,
+            resource_type: String,
+            $( $($attr)* $field: $fty, )*
+            #[serde(flatten)]
+            extra: std::collections::HashMap<String, serde_json::Value>,
+        }
+
+        impl $name {
+            pub fn resource_type(&self) -> &str { &self.resource_type }
+            $( pub fn $field(&self) -> &$fty { &self.$field } )*
+            pub fn extra(&self) -> &std::collections::HashMap<String, serde_json::Value> { &self.extra }
+        }
+    };
+}
+
+/// Emits a model struct with public fields and the same accessors, for the `public-fields` feature
+#[cfg(feature = "public-fields")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __model_struct {
+    ($name:ident [$( { $($attr:tt)* } $field:ident: $fty:ty, )*]) => {
+        #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+        pub struct $name {
+            pub gid: String,
+            pub resource_type: String,
+            $( $($attr)* pub $field: $fty, )*
+            #[serde(flatten)]
+            pub extra: std::collections::HashMap<String, serde_json::Value>,
+        }
+
+        impl $name {
+            pub fn resource_type(&self) -> &str { &self.resource_type }
+            $( pub fn $field(&self) -> &$fty { &self.$field } )*
+            pub fn extra(&self) -> &std::collections::HashMap<String, serde_json::Value> { &self.extra }
+        }
+    };
+}
+
+model!(StatusUpdate "status_updates" {
+    status_type: String,
+    title: Option<String>,
+    text: String,
+});
+
+model!(UserTaskList "user_task_lists" {
+    name: String,
+});
+
+model!(ProjectTemplate "project_templates" {
+    name: String,
+});
+
+model!(Job "jobs" {
+    status: String,
+    #[serde(default)]
+    new_project: Option<serde_json::Value>,
+    #[serde(default)]
+    new_task: Option<serde_json::Value>,
+});
+
+impl Job {
+    /// Whether the job has either succeeded or failed, and won't change anymore
+    pub fn is_finished(&self) -> bool {
+        self.status == "succeeded" || self.status == "failed"
+    }
+}
+
+model!(CustomFieldValue "custom_fields" {
+    name: Option<String>,
+    resource_subtype: Option<String>,
+    /// The value of any type of field as text, as shown in Asana
+    display_value: Option<String>,
+    #[serde(default)]
+    enum_value: Option<EnumOption>,
+    #[serde(default)]
+    multi_enum_values: Vec<EnumOption>,
+    #[serde(default, deserialize_with = "crate::de::number_or_string")]
+    number_value: Option<f64>,
+    text_value: Option<String>,
+    date_value: Option<DateValue>,
+});
+
+/// The value of a `date` custom field, with a time if one was set
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DateValue {
+    pub date: String,
+    #[serde(default)]
+    pub date_time: Option<String>,
+}
+
+/// An option of an `enum` or `multi_enum` custom field
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EnumOption {
+    pub gid: String,
+    pub name: String,
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default = "enabled")]
+    pub enabled: bool,
+}
+
+fn enabled() -> bool {
+    true
+}
+
+/// Models with a list of custom field values, usually declared with the `..CustomFields` preset of [`model!`]
+pub trait CustomFields {
+    fn custom_fields(&self) -> &[CustomFieldValue];
+
+    /// The selected option of an `enum` custom field, if the field is set on this entity
+    fn custom_field_enum(&self, field_gid: &str) -> Option<EnumOption> {
+        self.custom_fields().iter()
+            .find(|field| field.gid == field_gid)
+            .and_then(|field| field.enum_value.clone())
+    }
+}
+
+model!(OrganizationExport "organization_exports" {
+    state: String,
+    #[serde(default)]
+    download_url: Option<String>,
+});
+
+impl OrganizationExport {
+    /// Whether the export has either finished or failed, and won't change anymore
+    pub fn is_finished(&self) -> bool {
+        self.state == "finished" || self.state == "error"
+    }
+
+    pub(crate) fn into_download_url(self) -> Option<String> {
+        self.download_url
+    }
+}
+
+/// A field which differs between two states of an entity, as reported by the `diff()` method of models
+///
+/// ```
+/// # use asana_sdk::model;
+/// use asana_sdk::models::{FieldChange, Model};
+/// use serde_json::json;
+///
+/// model!(Task "tasks" { name: String, completed: bool });
+///
+/// let before = Task::from_value(json!({ "gid": "1", "resource_type": "task", "name": "Draft", "completed": false })).unwrap();
+/// let after = Task::from_value(json!({ "gid": "1", "resource_type": "task", "name": "Draft", "completed": true, "color": "red" })).unwrap();
+///
+/// assert_eq!(before.diff(&after), vec![
+///     FieldChange { field: String::from("completed"), old: json!(false), new: json!(true) },
+///     FieldChange { field: String::from("color"), old: serde_json::Value::Null, new: json!("red") },
+/// ]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: serde_json::Value,
+    pub new: serde_json::Value,
+}
+
+impl FieldChange {
+    #[doc(hidden)]
+    pub fn new<T: Serialize>(field: &str, old: &T, new: &T) -> FieldChange {
+        FieldChange {
+            field: field.to_string(),
+            old: serde_json::to_value(old).unwrap_or(serde_json::Value::Null),
+            new: serde_json::to_value(new).unwrap_or(serde_json::Value::Null),
+        }
+    }
+
+    /// Compare two `extra` maps, in which a missing key counts as `null`
+    #[doc(hidden)]
+    pub fn diff_extra(old: &HashMap<String, serde_json::Value>, new: &HashMap<String, serde_json::Value>, changes: &mut Vec<FieldChange>) {
+        let mut keys: Vec<&String> = old.keys().chain(new.keys().filter(|key| !old.contains_key(*key))).collect();
+        keys.sort();
+
+        for key in keys {
+            let (old, new) = (old.get(key).unwrap_or(&serde_json::Value::Null), new.get(key).unwrap_or(&serde_json::Value::Null));
+            if old != new {
+                changes.push(FieldChange { field: key.clone(), old: old.clone(), new: new.clone() });
+            }
+        }
+    }
+}
+
+/// A compact reference to a related entity, as nested in other responses
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Compact {
+    pub gid: String,
+    #[serde(default)]
+    pub resource_type: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// A task's placement within a project, and the section it sits in within that project
+///
+/// Declare `memberships: Vec<Membership>` on a task model to know which section a task is in, per project.
+/// ```
+/// # use asana_sdk::model;
+/// use asana_sdk::models::{Membership, Model};
+///
+/// model!(Task "tasks" { memberships: Vec<Membership> });
+///
+/// let task = Task::from_value(serde_json::json!({
+///     "gid": "1", "resource_type": "task",
+///     "memberships": [{
+///         "project": { "gid": "2", "resource_type": "project", "name": "Roadmap" },
+///         "section": { "gid": "3", "resource_type": "section", "name": "Next up" }
+///     }]
+/// })).unwrap();
+///
+/// assert_eq!(task.memberships()[0].section.as_ref().unwrap().gid, "3");
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Membership {
+    pub project: Compact,
+    #[serde(default)]
+    pub section: Option<Compact>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 pub trait Model: DeserializeOwned {
     fn endpoint() -> String;
+    fn gid(&self) -> &str;
     fn field_names() -> &'static [&'static str];
     fn opt_strings() -> Vec<String>;
+
+    /// A human-readable singular name for the resource, derived from its endpoint, e.g. for UI labels
+    ///
+    /// ```
+    /// # use asana_sdk::model;
+    /// use asana_sdk::models::{Model, StatusUpdate};
+    ///
+    /// model!(Task "tasks" {});
+    /// model!(Story "stories" {});
+    /// model!(ProjectStatus "project_statuses" {});
+    ///
+    /// assert_eq!(Task::resource_name(), "task");
+    /// assert_eq!(Story::resource_name(), "story");
+    /// assert_eq!(StatusUpdate::resource_name(), "status update");
+    /// assert_eq!(ProjectStatus::resource_name(), "project status");
+    /// ```
+    fn resource_name() -> String {
+        let endpoint = Self::endpoint().replace('_', " ");
+        if let Some(stem) = endpoint.strip_suffix("ies") {
+            format!("{}y", stem)
+        } else if endpoint.ends_with("sses") || endpoint.ends_with("uses") {
+            endpoint[..endpoint.len() - 2].to_string()
+        } else {
+            endpoint.strip_suffix('s').unwrap_or(&endpoint).to_string()
+        }
+    }
+
+    /// Names of the fields which aren't declared on the model, and were collected into its `extra` map
+    fn extra_keys(&self) -> Vec<&str> {
+        Vec::new()
+    }
+
+    /// The `resource_subtype` sent when creating this model, unless the create body sets one itself
+    fn default_subtype() -> Option<&'static str> {
+        None
+    }
+
+    /// Build a model from JSON without an API call, e.g. for test fixtures
+    ///
+    /// ```
+    /// # use asana_sdk::model;
+    /// use asana_sdk::models::Model;
+    ///
+    /// model!(User "users" { name: String });
+    ///
+    /// let user = User::from_value(serde_json::json!({
+    ///     "gid": "12345678",
+    ///     "resource_type": "user",
+    ///     "name": "Greg Sanchez",
+    /// })).unwrap();
+    ///
+    /// assert_eq!(user.gid(), "12345678");
+    /// ```
+    fn from_value(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(value)
+    }
 }
 
-#[derive(Deserialize, Debug)]
+/// A model compared & hashed by its gid only, to put models in hash-based collections by identity
+///
+/// Models themselves compare all of their fields, so two fetches of a changed entity aren't equal.
+/// ```
+/// # use asana_sdk::model;
+/// use asana_sdk::models::{ByGid, Model};
+/// use std::collections::HashSet;
+///
+/// model!(Task "tasks" { name: String });
+///
+/// let before = Task::from_value(serde_json::json!({ "gid": "1", "resource_type": "task", "name": "Draft" })).unwrap();
+/// let after = Task::from_value(serde_json::json!({ "gid": "1", "resource_type": "task", "name": "Final" })).unwrap();
+///
+/// let tasks: HashSet<_> = vec![ByGid(before), ByGid(after)].into_iter().collect();
+/// assert_eq!(tasks.len(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ByGid<T>(pub T);
+
+impl<T: Model> PartialEq for ByGid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.gid() == other.0.gid()
+    }
+}
+
+impl<T: Model> Eq for ByGid<T> {}
+
+impl<T: Model> std::hash::Hash for ByGid<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.gid().hash(state);
+    }
+}
+
+impl<T> std::ops::Deref for ByGid<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
 pub(crate) struct Wrapper<T> {
     pub data: T,
 }
@@ -81,4 +615,11 @@ pub(crate) struct Wrapper<T> {
 #[derive(Deserialize, Debug)]
 pub(crate) struct ListWrapper<T> {
     pub data: Vec<T>,
+    #[serde(default)]
+    pub next_page: Option<NextPage>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct NextPage {
+    pub offset: String,
 }
\ No newline at end of file
@@ -0,0 +1,273 @@
+use crate::models::*;
+use crate::{opt_fields, AsanaError, Client};
+use futures::channel::mpsc;
+use futures::{SinkExt, StreamExt};
+use reqwest::Method;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// The largest page size Asana allows
+pub(crate) const PAGE_SIZE: &str = "100";
+
+/// Pages through a list endpoint, following Asana's `next_page` offsets
+///
+/// The endpoint, including any relational scope set with `from()`, is captured when the pager is created,
+/// so every page is fetched from the same endpoint. This also means the client holds no pending state
+/// while paging, so a pager (or a `list_all()` future) can be dropped halfway without affecting later calls.
+pub struct Pages<'a, T> {
+    client: &'a mut Client,
+    path: String,
+    params: Vec<(String, String)>,
+    headers: Vec<(String, String)>,
+    opt_fields: String,
+    extra: Vec<String>,
+    offset: Option<String>,
+    done: bool,
+    prefetched: Option<mpsc::Receiver<Result<Vec<T>, AsanaError>>>,
+    model: PhantomData<T>,
+}
+
+/// Just the gid of an entity of any type, for cheap passes over a list
+#[derive(Deserialize)]
+struct GidOnly {
+    gid: String,
+}
+
+impl Model for GidOnly {
+    fn endpoint() -> String { String::new() }
+
+    fn gid(&self) -> &str { &self.gid }
+
+    fn field_names() -> &'static [&'static str] { &[] }
+
+    fn opt_strings() -> Vec<String> { Vec::new() }
+}
+
+impl<'a, T: Model> Pages<'a, T> {
+    /// Fetch the next page, or `None` once all pages have been fetched
+    pub async fn next_page(&mut self) -> Option<Result<Vec<T>, AsanaError>> {
+        if self.done {
+            return None;
+        }
+
+        if let Some(prefetched) = &mut self.prefetched {
+            let page = prefetched.next().await;
+            self.done = !matches!(page, Some(Ok(_)));
+            return page;
+        }
+
+        match self.fetch().await {
+            Ok(page) => {
+                self.offset = page.next_page.map(|next| next.offset);
+                self.done = self.offset.is_none();
+                Some(Ok(page.data))
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+
+    /// Keep fetching up to `pages` pages ahead in the background, while the current page is being processed
+    ///
+    /// Asana only reveals where the next page starts once a page has arrived, so pages are still fetched
+    /// one after another, and are returned in order. Prefetching overlaps their latency with the caller's work,
+    /// at the cost of holding the prefetched pages in memory. `1` (the default) fetches each page on demand.
+    ///
+    /// The background fetches use a fork of the client, so they don't update its `last_headers()` or `skipped_items()`.
+    /// This must be called within a Tokio runtime.
+    /// ```no_run
+    /// # use asana_sdk::*;
+    /// # use asana_sdk::models::Model;
+    /// # async fn example() -> Result<(), AsanaError> {
+    /// # let mut asana = Asana::connect(String::from("1/your:personal-access-token"));
+    /// model!(Project "projects" {});
+    /// model!(Task "tasks" { name: String });
+    ///
+    /// let mut pages = asana.from::<Project>("12345678").pages::<Task>().prefetch(3);
+    /// while let Some(tasks) = pages.next_page().await {
+    ///     for task in tasks? {
+    ///         println!("{}", task.name());
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn prefetch(mut self, pages: usize) -> Pages<'a, T>
+    where
+        T: Send + 'static,
+    {
+        if pages <= 1 || self.done || self.prefetched.is_some() {
+            return self;
+        }
+
+        // The channel holds its buffer plus one page per sender, and the background task holds one more page
+        // while it waits to send, so this keeps at most `pages` pages ahead of the caller
+        let (mut sender, receiver) = mpsc::channel(pages - 2);
+        let mut client = self.client.fork();
+        client.params = self.params.clone();
+        client.headers = self.headers.clone();
+        let (path, opt_fields, extra, offset) = (self.path.clone(), self.opt_fields.clone(), self.extra.clone(), self.offset.clone());
+
+        tokio::spawn(async move {
+            let mut pager = client.pager::<T>(path, opt_fields, extra);
+            pager.offset = offset;
+
+            while let Some(page) = pager.next_page().await {
+                // Stop once the pages are no longer wanted
+                if sender.send(page).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        self.prefetched = Some(receiver);
+        self
+    }
+
+    /// Whether all pages have been fetched, or paging stopped on an error
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    async fn fetch(&mut self) -> Result<ListWrapper<T>, AsanaError> {
+        self.client.params = self.params.clone();
+        self.client.headers = self.headers.clone();
+        self.client.requested_extra = self.extra.clone();
+        self.client.traced_model = Some(std::any::type_name::<T>());
+        self.client.param("limit", PAGE_SIZE);
+        if let Some(offset) = &self.offset {
+            self.client.params.push((String::from("offset"), offset.clone()));
+        }
+
+        let response = self.client
+            .request(Method::GET, &self.path, Some(self.opt_fields.clone()), None).await?;
+
+        self.client.parse_list(response).await
+    }
+}
+
+impl Client {
+    /// Page through all entities of a list endpoint, one page at a time
+    ///
+    /// ```no_run
+    /// # use asana_sdk::*;
+    /// # use asana_sdk::models::Model;
+    /// # async fn example() -> Result<(), AsanaError> {
+    /// # let mut asana = Asana::connect(String::from("1/your:personal-access-token"));
+    /// model!(Task "tasks" {});
+    /// model!(Story "stories" { text: Option<String> });
+    ///
+    /// let mut pages = asana.from::<Task>("12345678").pages::<Story>();
+    /// while let Some(stories) = pages.next_page().await {
+    ///     println!("{} stories", stories?.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pages<T: Model>(&mut self) -> Pages<'_, T> {
+        let path = format!("{}{}/", self.endpoint, T::endpoint());
+        self.endpoint.clear();
+
+        let extra = self.next_opt_fields.take().unwrap_or_else(|| self.opt_fields.clone());
+        let opt_fields = opt_fields::<T>(&extra);
+
+        self.pager(path, opt_fields, extra)
+    }
+
+    /// Count all entities of a list endpoint, paging through them with only their gids
+    ///
+    /// This is a quick extra pass, e.g. to know the total for a progress bar before calling `list_all()`.
+    /// Like `list_all()`, it can be scoped to a relational endpoint with `from()`.
+    pub async fn count_all<T: Model>(&mut self) -> Result<usize, AsanaError> {
+        let path = format!("{}{}/", self.endpoint, T::endpoint());
+        self.endpoint.clear();
+        self.next_opt_fields = None;
+
+        let mut pages = self.pager::<GidOnly>(path, String::from("gid"), Vec::new());
+        let mut count = 0;
+
+        while let Some(page) = pages.next_page().await {
+            count += page?.len();
+        }
+
+        Ok(count)
+    }
+
+    /// List just the gids of all entities at a list endpoint
+    pub(crate) async fn list_gids(&mut self, path: String) -> Result<Vec<String>, AsanaError> {
+        let mut pages = self.pager::<GidOnly>(path, String::from("gid"), Vec::new());
+        let mut gids = Vec::new();
+
+        while let Some(page) = pages.next_page().await {
+            gids.extend(page?.into_iter().map(|entity| entity.gid));
+        }
+
+        Ok(gids)
+    }
+
+    fn pager<T: Model>(&mut self, path: String, opt_fields: String, extra: Vec<String>) -> Pages<'_, T> {
+        self.skipped.clear();
+
+        Pages {
+            path,
+            params: std::mem::take(&mut self.params),
+            headers: std::mem::take(&mut self.headers),
+            opt_fields,
+            extra,
+            offset: None,
+            done: false,
+            prefetched: None,
+            model: PhantomData,
+            client: self,
+        }
+    }
+
+    /// List all entities of a list endpoint, fetching every page
+    ///
+    /// Like `list()`, this can be scoped to a relational endpoint with `from()`,
+    /// e.g. `asana.from::<Task>(gid).list_all::<Story>()` for a task's full story history.
+    pub async fn list_all<T: Model>(&mut self) -> Result<Vec<T>, AsanaError> {
+        let mut pages = self.pages::<T>();
+        let mut data = Vec::new();
+
+        while let Some(page) = pages.next_page().await {
+            data.extend(page?);
+        }
+
+        Ok(data)
+    }
+
+    /// List all entities of a list endpoint, indexed by gid
+    ///
+    /// Entities which appear more than once (e.g. when the list shifted while paging) are only kept once.
+    pub async fn list_all_map<T: Model>(&mut self) -> Result<HashMap<String, T>, AsanaError> {
+        let data = self.list_all::<T>().await?;
+
+        Ok(data.into_iter().map(|item| (item.gid().to_string(), item)).collect())
+    }
+
+    /// Like `list_all()`, but stops fetching pages once `cancelled` is set, e.g. by another task
+    ///
+    /// The flag is checked before each page. Like `list_checked()`, the returned flag is `true` when more pages
+    /// were available, so results of a cancelled listing can't be mistaken for complete ones.
+    pub async fn list_all_cancellable<T: Model>(&mut self, cancelled: &AtomicBool) -> Result<(Vec<T>, bool), AsanaError> {
+        let mut pages = self.pages::<T>();
+        let mut data = Vec::new();
+
+        while !pages.is_done() {
+            if cancelled.load(Ordering::SeqCst) {
+                return Ok((data, true));
+            }
+
+            if let Some(page) = pages.next_page().await {
+                data.extend(page?);
+            }
+        }
+
+        Ok((data, false))
+    }
+}
@@ -0,0 +1,18 @@
+//! The essentials for working with the client, in one import
+//!
+//! ```no_run
+//! use asana_sdk::prelude::*;
+//!
+//! model!(User "users" { name: String });
+//!
+//! # async fn example() -> Result<(), AsanaError> {
+//! let mut asana = Asana::connect(String::from("1/your:personal-access-token"));
+//! let me = asana.get::<User>("me").await?;
+//! println!("{}", me.gid());
+//! # Ok(())
+//! # }
+//! ```
+
+pub use crate::model;
+pub use crate::models::{Compact, CustomFields, Membership, Model};
+pub use crate::{AccessLevel, ApprovalStatus, Asana, AsanaError, Client, ClientBuilder, CompletedFilter, CreateTask, Due, InsertPosition, MultiResult, Pages};
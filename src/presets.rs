@@ -0,0 +1,41 @@
+use crate::models::*;
+use crate::{dedup, parse, AsanaError, Client};
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+
+impl Client {
+    /// Register a named set of fields, for fetching entities with `get_preset()`
+    pub fn register_preset(&mut self, name: &str, fields: &[&str]) -> &mut Client {
+        self.presets.insert(name.to_string(), fields.iter().map(|f| f.to_string()).collect());
+        self
+    }
+
+    /// Get an entity by endpoint & gid, including only the fields of a registered preset
+    ///
+    /// This picks the fields by name rather than by model type, so one type (even a plain `serde_json::Value`)
+    /// can be used for several views of the same resource.
+    /// ```no_run
+    /// # use asana_sdk::*;
+    /// # async fn example() -> Result<(), AsanaError> {
+    /// # let mut asana = Asana::connect(String::from("1/your:personal-access-token"));
+    /// asana.register_preset("user_basic", &["name", "email"]);
+    ///
+    /// let me: serde_json::Value = asana.get_preset("users", "me", "user_basic").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_preset<T: DeserializeOwned>(&mut self, endpoint: &str, gid: &str, preset: &str) -> Result<T, AsanaError> {
+        let fields = match self.presets.get(preset) {
+            Some(fields) => dedup(std::iter::once("resource_type").chain(fields.iter().map(|f| f.as_str()))).join("|"),
+            None => return Err(AsanaError::Validation(format!("no field preset named {}", preset))),
+        };
+
+        let path = format!("{}/{}", endpoint, gid);
+        self.next_opt_fields = None;
+
+        let response = self.request(Method::GET, &path, Some(format!("this.({})", fields)), None).await?;
+        let entity: Wrapper<T> = parse(response).await?;
+
+        Ok(entity.data)
+    }
+}
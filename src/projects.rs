@@ -0,0 +1,196 @@
+use crate::models::*;
+use crate::{opt_fields, parse, validate_rich_text, AsanaError, Client};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+
+/// The access level of a project member, which can also be used as a model field type
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessLevel {
+    Admin,
+    Editor,
+    Commenter,
+    Viewer,
+}
+
+impl Client {
+    /// List the members of a project
+    pub async fn members<T: Model>(&mut self, project_gid: &str) -> Result<Vec<T>, AsanaError> {
+        self.relation("projects", project_gid, "members", T::field_names()).await
+    }
+
+    /// Add a user as a member of a project, with the given access level
+    pub async fn add_project_member(&mut self, project_gid: &str, user_gid: &str, access: AccessLevel) -> Result<(), AsanaError> {
+        let body = json!({
+            "members": [user_gid],
+            "access_level": access,
+        });
+
+        self.action("projects", project_gid, "addMembers", body).await?;
+        Ok(())
+    }
+
+    /// List every project in a workspace, paging through all results
+    ///
+    /// Pass `Some(archived)` to only list archived or unarchived projects.
+    pub async fn all_projects<T: Model>(&mut self, workspace_gid: &str, archived: Option<bool>) -> Result<Vec<T>, AsanaError> {
+        self.scope("workspaces", workspace_gid);
+        if let Some(archived) = archived {
+            self.param("archived", &archived.to_string());
+        }
+
+        self.list_all::<T>().await
+    }
+
+    /// List the projects in a workspace which are in the teams a user is a member of
+    ///
+    /// Asana has no endpoint for this, so it lists the user's teams first, and then the projects of each team
+    /// concurrently. Projects shared with the user directly, outside of their teams, aren't included, and like any
+    /// listing, only projects the token can see are returned.
+    ///
+    /// Plain workspaces (rather than organizations) have no teams, so all their projects are listed instead.
+    pub async fn projects_for_user<T: Model>(&mut self, user_gid: &str, workspace_gid: &str) -> Result<Vec<T>, AsanaError> {
+        let workspace = self.get::<WorkspaceKind>(workspace_gid).await?;
+        if !workspace.is_organization {
+            return self.all_projects::<T>(workspace_gid, None).await;
+        }
+
+        self.param("organization", workspace_gid);
+        let teams = self.list_gids(format!("users/{}/teams/", user_gid)).await?;
+
+        let teams: Vec<&str> = teams.iter().map(String::as_str).collect();
+        let mut per_team = self.list_from_many_at::<T>("teams", &teams).await?;
+
+        // Keep the order of the teams, and list projects shared between teams once
+        let mut seen = std::collections::HashSet::new();
+        Ok(teams.iter()
+            .flat_map(|team| per_team.remove(*team).unwrap_or_default())
+            .filter(|project| seen.insert(project.gid().to_string()))
+            .collect())
+    }
+
+    /// Map the gid of every task in a project to its value for one custom field
+    ///
+    /// Tasks are paged through with only their custom fields, so this is much lighter than listing full tasks.
+    /// Tasks without the field (e.g. when the field isn't added to the project) are left out.
+    pub async fn custom_field_values_for_project(&mut self, project_gid: &str, field_gid: &str) -> Result<HashMap<String, CustomFieldValue>, AsanaError> {
+        let mut pages = self.scope("projects", project_gid).pages::<TaskCustomFields>();
+        let mut values = HashMap::new();
+
+        while let Some(tasks) = pages.next_page().await {
+            for task in tasks? {
+                if let Some(value) = task.custom_fields.into_iter().find(|field| field.gid() == field_gid) {
+                    values.insert(task.gid, value);
+                }
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Post a status update (e.g. `on_track`, `at_risk`, `off_track`) on a project, portfolio or goal
+    pub async fn post_status_update(&mut self, parent_gid: &str, status_type: &str, text: &str) -> Result<StatusUpdate, AsanaError> {
+        self.create::<StatusUpdate>(json!({
+            "parent": parent_gid,
+            "status_type": status_type,
+            "text": text,
+        })).await
+    }
+
+    /// Create a new project in a team from a project template, returning the job which creates it
+    ///
+    /// Pass the job to `wait_for_job()` to wait until the project is ready.
+    pub async fn instantiate_project_template(&mut self, template_gid: &str, name: &str, team_gid: &str, public: bool) -> Result<Job, AsanaError> {
+        let path = format!("project_templates/{}/instantiateProject", template_gid);
+        let body = json!({
+            "name": name,
+            "team": team_gid,
+            "public": public,
+        });
+
+        let response = self.request(Method::POST, &path, Some(opt_fields::<Job>(&[])), Some(body)).await?;
+        let job: Wrapper<Job> = parse(response).await?;
+
+        Ok(job.data)
+    }
+
+    /// Move a section of a project before another section, or to the end of the project by passing `None`
+    pub async fn reorder_section(&mut self, project_gid: &str, section_gid: &str, before: Option<&str>) -> Result<(), AsanaError> {
+        let body = match before {
+            Some(before) => json!({ "section": section_gid, "before_section": before }),
+            None => {
+                // Asana needs a neighbouring section, so moving to the end means moving after the current last section,
+                // which may be many pages in
+                let sections = self.list_gids(format!("projects/{}/sections/", project_gid)).await?;
+
+                match sections.iter().rev().find(|gid| *gid != section_gid) {
+                    Some(last) => json!({ "section": section_gid, "after_section": last }),
+                    None => return Ok(()),
+                }
+            }
+        };
+
+        self.action("projects", project_gid, "sections/insert", body).await?;
+        Ok(())
+    }
+
+    /// Fetch the brief of a project, or `None` if the project has no brief
+    pub async fn project_brief<T: Model>(&mut self, project_gid: &str) -> Result<Option<T>, AsanaError> {
+        self.relation("projects", project_gid, "project_brief", T::field_names()).await
+    }
+
+    /// Create the brief of a project, from rich text wrapped in a `<body>` element
+    pub async fn create_project_brief<T: Model>(&mut self, project_gid: &str, title: &str, html_text: &str) -> Result<T, AsanaError> {
+        validate_rich_text(html_text)?;
+        self.scope("projects", project_gid).create::<T>(json!({
+            "title": title,
+            "html_text": html_text,
+        })).await
+    }
+
+    /// Replace the rich text of a project brief, which must be wrapped in a `<body>` element
+    pub async fn update_project_brief<T: Model>(&mut self, brief_gid: &str, html_text: &str) -> Result<T, AsanaError> {
+        validate_rich_text(html_text)?;
+        self.update::<T>(brief_gid, json!({ "html_text": html_text })).await
+    }
+}
+
+/// Just the custom fields of a task
+#[derive(Deserialize)]
+struct TaskCustomFields {
+    gid: String,
+    #[serde(default)]
+    custom_fields: Vec<CustomFieldValue>,
+}
+
+impl Model for TaskCustomFields {
+    fn endpoint() -> String { String::from("tasks") }
+
+    fn gid(&self) -> &str { &self.gid }
+
+    fn field_names() -> &'static [&'static str] { &["resource_type", "custom_fields"] }
+
+    fn opt_strings() -> Vec<String> {
+        vec![format!("{}.(gid|{})", CustomFieldValue::endpoint(), CustomFieldValue::field_names().join("|"))]
+    }
+}
+
+/// Just whether a workspace is an organization, which has teams
+#[derive(Deserialize)]
+struct WorkspaceKind {
+    gid: String,
+    #[serde(default)]
+    is_organization: bool,
+}
+
+impl Model for WorkspaceKind {
+    fn endpoint() -> String { String::from("workspaces") }
+
+    fn gid(&self) -> &str { &self.gid }
+
+    fn field_names() -> &'static [&'static str] { &["resource_type", "is_organization"] }
+
+    fn opt_strings() -> Vec<String> { Vec::new() }
+}
@@ -0,0 +1,97 @@
+use crate::models::*;
+use crate::{AsanaError, Client};
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Response, StatusCode};
+use serde_json::Value;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// When and how long to wait before retrying a failed request
+#[derive(Debug, Clone)]
+pub(crate) struct RetryPolicy {
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub max_retries: u32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            max_retries: 3,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The delay before the next attempt, or `None` if the response should be returned as-is
+    ///
+    /// Only `429 Too Many Requests` and `5xx` responses are retried. Rate limited responses wait for
+    /// Asana's `Retry-After`, server errors back off exponentially with jitter.
+    ///
+    /// A write may already have been applied when a server error is returned, so server errors are only retried
+    /// for `replayable` requests: reads, and writes with an idempotency key. Rate limited requests were never applied.
+    pub fn delay(&self, attempt: u32, response: &Response, replayable: bool) -> Option<Duration> {
+        let status = response.status();
+        let retryable = status == StatusCode::TOO_MANY_REQUESTS || (status.is_server_error() && replayable);
+        if attempt >= self.max_retries || !retryable {
+            return None;
+        }
+
+        let retry_after = response.headers().get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok());
+
+        match retry_after {
+            Some(seconds) if status == StatusCode::TOO_MANY_REQUESTS => Some(Duration::from_secs(seconds)),
+            _ => Some(self.backoff(attempt)),
+        }
+    }
+
+    // Exponential backoff capped at max_delay, randomized within its upper half
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self.base_delay
+            .checked_mul(2u32.saturating_pow(attempt))
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.subsec_nanos()).unwrap_or(0);
+        let jitter = f64::from(nanos % 1000) / 1000.0;
+
+        delay / 2 + delay.mul_f64(jitter / 2.0)
+    }
+}
+
+impl Client {
+    /// Read-modify-write an entity, re-fetching it and re-applying the change when a concurrent edit conflicts
+    ///
+    /// `change` builds the update body from the current state of the entity, and is called again for each attempt.
+    /// After `attempts` conflicts in a row, the last `AsanaError::Conflict` is returned.
+    /// ```no_run
+    /// # use asana_sdk::*;
+    /// # use asana_sdk::models::Model;
+    /// # async fn example() -> Result<(), AsanaError> {
+    /// # let mut asana = Asana::connect(String::from("1/your:personal-access-token"));
+    /// model!(Task "tasks" { notes: String });
+    ///
+    /// let task = asana.update_with_retry::<Task, _>("12345678", 3, |task| {
+    ///     serde_json::json!({ "notes": format!("{}\nChecked by the sync worker", task.notes()) })
+    /// }).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn update_with_retry<T, F>(&mut self, gid: &str, attempts: u32, mut change: F) -> Result<T, AsanaError>
+    where
+        T: Model,
+        F: FnMut(&T) -> Value,
+    {
+        let mut attempt = 1;
+        loop {
+            let current = self.without_cache().get::<T>(gid).await?;
+            match self.update::<T>(gid, change(&current)).await {
+                Err(AsanaError::Conflict { .. }) if attempt < attempts => attempt += 1,
+                result => return result,
+            }
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use crate::AsanaError;
+use serde_json::Value;
+
+/// Check that rich text (like `html_notes`) is wrapped in the single `<body>` element Asana requires
+///
+/// This is only a light check for the most common mistake, Asana still validates the tags used within the body.
+/// ```
+/// use asana_sdk::validate_rich_text;
+///
+/// assert!(validate_rich_text("<body>Some <strong>bold</strong> text</body>").is_ok());
+/// assert!(validate_rich_text("Some <strong>bold</strong> text").is_err());
+/// ```
+pub fn validate_rich_text(html: &str) -> Result<(), AsanaError> {
+    let html = html.trim();
+    let inner = html.strip_prefix("<body>").and_then(|html| html.strip_suffix("</body>"));
+
+    match inner {
+        Some(inner) if !inner.contains("<body>") && !inner.contains("</body>") => Ok(()),
+        _ => Err(AsanaError::Validation(String::from(
+            "rich text must be wrapped in a single <body>...</body> element"
+        ))),
+    }
+}
+
+/// Check that a request body sets at most one of `notes` (plain text) and `html_notes` (rich text)
+///
+/// Both describe the same description, so sending both leaves it up to Asana which one wins.
+/// Every write is checked before it's sent.
+/// ```
+/// use asana_sdk::validate_notes;
+/// use serde_json::json;
+///
+/// assert!(validate_notes(&json!({ "notes": "Plain text" })).is_ok());
+/// assert!(validate_notes(&json!({ "html_notes": "<body>Rich text</body>" })).is_ok());
+/// assert!(validate_notes(&json!({ "notes": "Plain text", "html_notes": "<body>Rich text</body>" })).is_err());
+/// ```
+pub fn validate_notes(data: &Value) -> Result<(), AsanaError> {
+    if data.get("notes").is_some() && data.get("html_notes").is_some() {
+        return Err(AsanaError::Validation(String::from(
+            "only one of notes and html_notes can be set at once"
+        )));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use crate::models::*;
+use crate::{parse, AsanaError, Client};
+use futures::stream::{self, Stream, StreamExt};
+use reqwest::Method;
+
+/// Asana's advanced search returns at most this many tasks, without a way to page beyond them
+const SEARCH_LIMIT: usize = 100;
+
+/// An item of `search_tasks_stream()`
+#[derive(Debug)]
+pub enum SearchResult<T> {
+    Task(T),
+    /// Comes after the last task when the search hit its limit (at most 100 tasks), so more tasks may have matched
+    Truncated,
+}
+
+/// A task which looks like a possible duplicate, by name
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimilarTask {
+    pub gid: String,
+    pub name: String,
+    /// Between 0 (nothing alike) and 1 (identical names)
+    pub similarity: f32,
+}
+
+/// The similarity of two names, as a normalized Levenshtein distance between 0 and 1
+///
+/// Case & surrounding whitespace are ignored.
+/// ```
+/// use asana_sdk::similarity;
+///
+/// assert_eq!(similarity("Write docs", "write docs "), 1.0);
+/// assert!(similarity("Write docs", "Write tests") > 0.6);
+/// assert!(similarity("Write docs", "Buy milk") < 0.2);
+/// assert_eq!(similarity("", ""), 1.0);
+/// ```
+pub fn similarity(a: &str, b: &str) -> f32 {
+    let a: Vec<char> = a.trim().to_lowercase().chars().collect();
+    let b: Vec<char> = b.trim().to_lowercase().chars().collect();
+
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    1.0 - levenshtein(&a, &b) as f32 / longest as f32
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + if ca == cb { 0 } else { 1 };
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+impl Client {
+    /// Find tasks in a workspace with names similar to `name`, most similar first
+    ///
+    /// Candidates come from Asana's typeahead search, and are scored on similarity of their names.
+    /// Only tasks scoring at least `threshold` (between 0 and 1) are returned.
+    pub async fn find_similar_tasks(&mut self, workspace_gid: &str, name: &str, threshold: f32) -> Result<Vec<SimilarTask>, AsanaError> {
+        let path = format!("workspaces/{}/typeahead", workspace_gid);
+        let response = self
+            .param("resource_type", "task")
+            .param("query", name)
+            .param("count", "100")
+            .request(Method::GET, &path, Some(String::from("name")), None).await?;
+        let candidates: ListWrapper<Compact> = parse(response).await?;
+
+        let mut similar: Vec<SimilarTask> = candidates.data.into_iter()
+            .filter_map(|task| {
+                let candidate = task.name?;
+                let score = similarity(name, &candidate);
+                Some(SimilarTask { gid: task.gid, name: candidate, similarity: score })
+            })
+            .filter(|task| task.similarity >= threshold)
+            .collect();
+
+        similar.sort_by(|a, b| b.similarity.partial_cmp(&a.similarity).unwrap_or(std::cmp::Ordering::Equal));
+        Ok(similar)
+    }
+
+    /// Stream the tasks matching an advanced search, with a final `SearchResult::Truncated` if there may be more
+    ///
+    /// The query is passed as-is, as in `[("text", "invoice"), ("completed", "false")]`, with `limit=100` unless it sets a limit.
+    /// Asana returns at most 100 tasks for a search, so on truncation, narrow the query to find the rest,
+    /// for example by `created_at.before` the oldest task with `sort_by=created_at`.
+    /// ```no_run
+    /// # use asana_sdk::{prelude::*, SearchResult};
+    /// # use futures::StreamExt;
+    /// # model!(Task "tasks" { name: String });
+    /// # async fn example() -> Result<(), AsanaError> {
+    /// # let mut asana = Asana::connect(String::from("1/your:personal-access-token"));
+    /// let mut results = Box::pin(asana.search_tasks_stream::<Task>("12345678", &[("text", "invoice")]));
+    /// while let Some(result) = results.next().await {
+    ///     match result? {
+    ///         SearchResult::Task(task) => println!("{}", task.name()),
+    ///         SearchResult::Truncated => println!("More tasks matched, refine the search to see them"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn search_tasks_stream<'a, T: Model + 'a>(&'a mut self, workspace_gid: &str, query: &[(&str, &str)]) -> impl Stream<Item = Result<SearchResult<T>, AsanaError>> + 'a {
+        let path = format!("workspaces/{}/tasks/search", workspace_gid);
+        let query: Vec<(String, String)> = query.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+
+        // Ask for the most Asana allows, unless the caller picked a limit, and compare against whichever is used
+        let limit = match query.iter().find(|(key, _)| key == "limit") {
+            Some((_, limit)) => limit.parse().unwrap_or(SEARCH_LIMIT),
+            None => SEARCH_LIMIT,
+        };
+
+        stream::once(async move {
+            for (key, value) in &query {
+                self.param(key, value);
+            }
+            if !query.iter().any(|(key, _)| key == "limit") {
+                self.param("limit", &SEARCH_LIMIT.to_string());
+            }
+
+            self.skipped.clear();
+            let response = self.call_at::<T>(Method::GET, &path, None).await?;
+            let tasks: ListWrapper<T> = self.parse_list(response).await?;
+
+            Ok(tasks.data)
+        }).flat_map(move |result: Result<Vec<T>, AsanaError>| {
+            let items = match result {
+                Ok(tasks) => {
+                    let truncated = tasks.len() >= limit;
+                    let mut items: Vec<_> = tasks.into_iter().map(|task| Ok(SearchResult::Task(task))).collect();
+                    if truncated {
+                        items.push(Ok(SearchResult::Truncated));
+                    }
+                    items
+                }
+                Err(e) => vec![Err(e)],
+            };
+
+            stream::iter(items)
+        })
+    }
+}
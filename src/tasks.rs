@@ -0,0 +1,467 @@
+use crate::models::*;
+use crate::{parse, validate_rich_text, AsanaError, Client};
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+/// The `approval_status` of an approval task, which can also be used as a model field type
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalStatus {
+    Pending,
+    Approved,
+    Rejected,
+    ChangesRequested,
+}
+
+/// A due (or start) date, either a whole day (`due_on`) or a specific moment (`due_at`)
+///
+/// Asana rejects updates which set both fields, so only the field matching the variant is sent.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Due {
+    Date(NaiveDate),
+    DateTime(DateTime<Utc>),
+}
+
+impl Due {
+    /// Build an update body setting either the date or the datetime field, never both
+    pub(crate) fn body(&self, date_field: &str, datetime_field: &str) -> Value {
+        let mut body = Map::new();
+        match self {
+            Due::Date(date) => body.insert(date_field.to_string(), json!(date.format("%Y-%m-%d").to_string())),
+            Due::DateTime(datetime) => body.insert(datetime_field.to_string(), json!(datetime.to_rfc3339_opts(SecondsFormat::Millis, true))),
+        };
+
+        Value::Object(body)
+    }
+}
+
+/// Where to place a task when adding it to a project, section or parent task
+#[derive(Debug, Clone, PartialEq)]
+pub enum InsertPosition {
+    /// Let Asana decide, which appends the task
+    Default,
+    /// Insert at the beginning of the list
+    Start,
+    /// Insert at the end of the list
+    End,
+    /// Insert before the task with this gid
+    Before(String),
+    /// Insert after the task with this gid
+    After(String),
+}
+
+impl InsertPosition {
+    /// Add the `insert_before` / `insert_after` fields for this position to a request body
+    pub(crate) fn apply(&self, body: &mut Map<String, Value>) {
+        match self {
+            InsertPosition::Default => {}
+            InsertPosition::Start => { body.insert("insert_after".to_string(), Value::Null); }
+            InsertPosition::End => { body.insert("insert_before".to_string(), Value::Null); }
+            InsertPosition::Before(gid) => { body.insert("insert_before".to_string(), json!(gid)); }
+            InsertPosition::After(gid) => { body.insert("insert_after".to_string(), json!(gid)); }
+        }
+    }
+}
+
+/// The body of a fully formed new task, including the projects, tags & followers it's created with, in one call
+///
+/// ```
+/// use asana_sdk::CreateTask;
+/// use serde_json::json;
+///
+/// let task = CreateTask::new("Write docs")
+///     .workspace("1111")
+///     .project("2222")
+///     .project_section("3333", "4444")
+///     .tag("5555")
+///     .follower("6666")
+///     .assignee("me");
+///
+/// assert_eq!(task.body(), json!({
+///     "name": "Write docs",
+///     "workspace": "1111",
+///     "projects": ["2222"],
+///     "memberships": [{ "project": "3333", "section": "4444" }],
+///     "tags": ["5555"],
+///     "followers": ["6666"],
+///     "assignee": "me",
+/// }));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CreateTask {
+    fields: Map<String, Value>,
+    projects: Vec<String>,
+    memberships: Vec<Value>,
+    tags: Vec<String>,
+    followers: Vec<String>,
+}
+
+impl CreateTask {
+    pub fn new(name: &str) -> CreateTask {
+        CreateTask::default().field("name", json!(name))
+    }
+
+    /// The workspace to create the task in, needed unless it's created in a project
+    pub fn workspace(self, workspace_gid: &str) -> CreateTask {
+        self.field("workspace", json!(workspace_gid))
+    }
+
+    /// Add the task to a project
+    pub fn project(mut self, project_gid: &str) -> CreateTask {
+        self.projects.push(project_gid.to_string());
+        self
+    }
+
+    /// Add the task to a section of a project
+    pub fn project_section(mut self, project_gid: &str, section_gid: &str) -> CreateTask {
+        self.memberships.push(json!({ "project": project_gid, "section": section_gid }));
+        self
+    }
+
+    pub fn tag(mut self, tag_gid: &str) -> CreateTask {
+        self.tags.push(tag_gid.to_string());
+        self
+    }
+
+    pub fn follower(mut self, user_gid: &str) -> CreateTask {
+        self.followers.push(user_gid.to_string());
+        self
+    }
+
+    pub fn assignee(self, user_gid: &str) -> CreateTask {
+        self.field("assignee", json!(user_gid))
+    }
+
+    pub fn notes(self, notes: &str) -> CreateTask {
+        self.field("notes", json!(notes))
+    }
+
+    pub fn due(mut self, due: Due) -> CreateTask {
+        if let Value::Object(fields) = due.body("due_on", "due_at") {
+            self.fields.extend(fields);
+        }
+        self
+    }
+
+    /// Set any other field of the task
+    pub fn field(mut self, name: &str, value: Value) -> CreateTask {
+        self.fields.insert(name.to_string(), value);
+        self
+    }
+
+    /// The `data` to create the task with
+    pub fn body(&self) -> Value {
+        let mut body = self.fields.clone();
+        if !self.projects.is_empty() {
+            body.insert("projects".to_string(), json!(self.projects));
+        }
+        if !self.memberships.is_empty() {
+            body.insert("memberships".to_string(), json!(self.memberships));
+        }
+        if !self.tags.is_empty() {
+            body.insert("tags".to_string(), json!(self.tags));
+        }
+        if !self.followers.is_empty() {
+            body.insert("followers".to_string(), json!(self.followers));
+        }
+
+        Value::Object(body)
+    }
+}
+
+impl Client {
+    /// Create a task with all of its relations at once, instead of a create followed by several actions
+    pub async fn create_task<T: Model>(&mut self, task: &CreateTask) -> Result<T, AsanaError> {
+        self.create::<T>(task.body()).await
+    }
+
+    /// List all tasks within a section, via the `/sections/{gid}/tasks` endpoint, following every page
+    pub async fn tasks_in_section<T: Model>(&mut self, section_gid: &str) -> Result<Vec<T>, AsanaError> {
+        self.scope("sections", section_gid).list_all::<T>().await
+    }
+
+    /// List all tasks with a tag, via the `/tags/{gid}/tasks` endpoint, following every page
+    pub async fn tasks_with_tag<T: Model>(&mut self, tag_gid: &str) -> Result<Vec<T>, AsanaError> {
+        self.scope("tags", tag_gid).list_all::<T>().await
+    }
+
+    /// List the followers of a task
+    pub async fn followers<T: Model>(&mut self, task_gid: &str) -> Result<Vec<T>, AsanaError> {
+        self.relation("tasks", task_gid, "followers", T::field_names()).await
+    }
+
+    /// List the gids of the projects a task is in, which is much cheaper than including the projects
+    pub async fn project_gids_for_task(&mut self, task_gid: &str) -> Result<Vec<String>, AsanaError> {
+        self.list_gids(format!("tasks/{}/projects/", task_gid)).await
+    }
+
+    /// Get the parent of a subtask, or `None` for a top-level task
+    pub async fn parent<T: Model>(&mut self, task_gid: &str) -> Result<Option<T>, AsanaError> {
+        self.relation("tasks", task_gid, "parent", T::field_names()).await
+    }
+
+    /// Fetch a task together with its full story history (comments & activity), concurrently
+    ///
+    /// Stories are fetched with `list_all()`, following every page, so the history of busy tasks isn't cut off
+    /// after the first page. Any page failing fails the whole call, rather than returning a partial history.
+    pub async fn task_with_history<T: Model, S: Model>(&mut self, task_gid: &str) -> Result<(T, Vec<S>), AsanaError> {
+        let mut history = self.fork();
+        let stories = history.scope("tasks", task_gid).list_all::<S>();
+
+        let (task, stories) = futures::future::join(self.get::<T>(task_gid), stories).await;
+
+        Ok((task?, stories?))
+    }
+
+    /// Assign a task to a user, or clear the assignee by passing `None`
+    pub async fn set_assignee<T: Model>(&mut self, task_gid: &str, user_gid: Option<&str>) -> Result<T, AsanaError> {
+        self.update::<T>(task_gid, json!({ "assignee": user_gid })).await
+    }
+
+    /// Set the due date of a task, as either a date or a datetime
+    pub async fn set_due<T: Model>(&mut self, task_gid: &str, due: Due) -> Result<T, AsanaError> {
+        self.update::<T>(task_gid, due.body("due_on", "due_at")).await
+    }
+
+    /// Set the start date of a task, as either a date (`start_on`) or a datetime (`start_at`)
+    ///
+    /// Asana only accepts a start date on tasks which also have a due date.
+    pub async fn set_start<T: Model>(&mut self, task_gid: &str, start: Due) -> Result<T, AsanaError> {
+        self.update::<T>(task_gid, start.body("start_on", "start_at")).await
+    }
+
+    /// Set the approval status of an approval task
+    pub async fn set_approval_status<T: Model>(&mut self, task_gid: &str, status: ApprovalStatus) -> Result<T, AsanaError> {
+        self.update::<T>(task_gid, json!({ "approval_status": status })).await
+    }
+
+    /// Set the plain text description of a task, replacing any rich text description
+    pub async fn set_notes<T: Model>(&mut self, task_gid: &str, notes: &str) -> Result<T, AsanaError> {
+        self.update::<T>(task_gid, json!({ "notes": notes })).await
+    }
+
+    /// Set the rich text description of a task, checking it's wrapped in a `<body>` element first
+    pub async fn set_html_notes<T: Model>(&mut self, task_gid: &str, html: &str) -> Result<T, AsanaError> {
+        validate_rich_text(html)?;
+        self.update::<T>(task_gid, json!({ "html_notes": html })).await
+    }
+
+    /// Mark a task as completed
+    pub async fn complete_task<T: Model>(&mut self, task_gid: &str) -> Result<T, AsanaError> {
+        self.update::<T>(task_gid, json!({ "completed": true })).await
+    }
+
+    /// Mark a completed task as incomplete again
+    pub async fn reopen_task<T: Model>(&mut self, task_gid: &str) -> Result<T, AsanaError> {
+        self.update::<T>(task_gid, json!({ "completed": false })).await
+    }
+
+    /// Add a task to a project, optionally within a section, at the given position
+    ///
+    /// Asana doesn't return the placement, so the task's memberships are read back afterwards.
+    /// The membership for the project is returned, or `None` if it isn't visible yet.
+    pub async fn add_task_to_project(&mut self, task_gid: &str, project_gid: &str, section: Option<&str>, position: InsertPosition) -> Result<Option<Membership>, AsanaError> {
+        let mut body = Map::new();
+        body.insert("project".to_string(), json!(project_gid));
+        if let Some(section) = section {
+            body.insert("section".to_string(), json!(section));
+        }
+        position.apply(&mut body);
+
+        self.action("tasks", task_gid, "addProject", Value::Object(body)).await?;
+
+        let memberships: Vec<Membership> = self.relation("tasks", task_gid, "memberships", &["project", "section"]).await?;
+        Ok(memberships.into_iter().find(|membership| membership.project.gid == project_gid))
+    }
+
+    /// Move a task from one project to another, optionally into a section of the new project
+    ///
+    /// The task is added to the new project first. If removing it from the old project then fails, it's removed
+    /// from the new project again, and the original error is returned. If that fails as well, both errors are
+    /// returned as `AsanaError::RollbackFailed`, and the task is left in both projects.
+    /// If the task was already in the new project, it's kept there rather than rolled back.
+    pub async fn move_task_between_projects(&mut self, task_gid: &str, from_project: &str, to_project: &str, section: Option<&str>) -> Result<(), AsanaError> {
+        if from_project == to_project {
+            return Err(AsanaError::Validation(format!("can't move task {} from project {} to itself", task_gid, to_project)));
+        }
+
+        // A membership which existed before the move isn't ours to roll back
+        let already_added = self.project_gids_for_task(task_gid).await?.iter().any(|gid| gid == to_project);
+
+        let mut body = Map::new();
+        body.insert("project".to_string(), json!(to_project));
+        if let Some(section) = section {
+            body.insert("section".to_string(), json!(section));
+        }
+        self.action("tasks", task_gid, "addProject", Value::Object(body)).await?;
+
+        let error = match self.remove_task_from_project(task_gid, from_project).await {
+            Ok(()) => return Ok(()),
+            Err(error) => error,
+        };
+
+        if already_added {
+            return Err(error);
+        }
+
+        match self.remove_task_from_project(task_gid, to_project).await {
+            Ok(()) => Err(error),
+            Err(rollback) => Err(AsanaError::RollbackFailed { error: Box::new(error), rollback: Box::new(rollback) }),
+        }
+    }
+
+    /// Remove a task from a project, which leaves the task itself in place
+    pub async fn remove_task_from_project(&mut self, task_gid: &str, project_gid: &str) -> Result<(), AsanaError> {
+        self.action("tasks", task_gid, "removeProject", json!({ "project": project_gid })).await?;
+        Ok(())
+    }
+
+    /// Add a tag to a task
+    pub async fn add_tag_to_task(&mut self, task_gid: &str, tag_gid: &str) -> Result<(), AsanaError> {
+        self.action("tasks", task_gid, "addTag", json!({ "tag": tag_gid })).await?;
+        Ok(())
+    }
+
+    /// Remove a tag from a task
+    pub async fn remove_tag_from_task(&mut self, task_gid: &str, tag_gid: &str) -> Result<(), AsanaError> {
+        self.action("tasks", task_gid, "removeTag", json!({ "tag": tag_gid })).await?;
+        Ok(())
+    }
+
+    /// Add users as followers of a task
+    pub async fn add_followers(&mut self, task_gid: &str, user_gids: &[&str]) -> Result<(), AsanaError> {
+        self.action("tasks", task_gid, "addFollowers", json!({ "followers": user_gids })).await?;
+        Ok(())
+    }
+
+    /// Remove users from the followers of a task
+    pub async fn remove_followers(&mut self, task_gid: &str, user_gids: &[&str]) -> Result<(), AsanaError> {
+        self.action("tasks", task_gid, "removeFollowers", json!({ "followers": user_gids })).await?;
+        Ok(())
+    }
+
+    /// Mark a task as dependent on other tasks
+    pub async fn add_dependencies(&mut self, task_gid: &str, dependency_gids: &[&str]) -> Result<(), AsanaError> {
+        self.action("tasks", task_gid, "addDependencies", json!({ "dependencies": dependency_gids })).await?;
+        Ok(())
+    }
+
+    /// Remove dependencies from a task
+    pub async fn remove_dependencies(&mut self, task_gid: &str, dependency_gids: &[&str]) -> Result<(), AsanaError> {
+        self.action("tasks", task_gid, "removeDependencies", json!({ "dependencies": dependency_gids })).await?;
+        Ok(())
+    }
+
+    /// Create a new subtask of a task, through `POST /tasks/{gid}/subtasks`
+    ///
+    /// This endpoint doesn't take a position, so Asana decides where the subtask goes.
+    /// Use `create_subtask_at()` to place it, or `set_parent()` to move an existing task.
+    /// ```
+    /// # use asana_sdk::*;
+    /// # use asana_sdk::models::Model;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// model!(Task "tasks" { name: String });
+    ///
+    /// let mut asana = Asana::builder(String::from("1/your:personal-access-token")).dry_run(true).build();
+    /// let subtask = asana.create_subtask::<Task>("1111", serde_json::json!({ "name": "Write docs" })).await;
+    ///
+    /// match subtask {
+    ///     Err(AsanaError::DryRun(request)) => {
+    ///         assert!(request.url.starts_with("https://app.asana.com/api/1.0/tasks/1111/subtasks?"));
+    ///         assert_eq!(request.body, Some(serde_json::json!({ "data": { "name": "Write docs" } })));
+    ///     }
+    ///     _ => panic!("expected a dry run"),
+    /// }
+    /// # }
+    /// ```
+    pub async fn create_subtask<T: Model>(&mut self, parent_gid: &str, data: Value) -> Result<T, AsanaError> {
+        // The model is a task, so it can't be scoped with `from()` like models with a `subtasks` endpoint
+        self.create_at::<T>(&format!("tasks/{}/subtasks", parent_gid), data).await
+    }
+
+    /// Create a new subtask of a task at the given position, by creating it and then moving it with `setParent`
+    ///
+    /// These are two calls, and the created subtask is returned as it was before it was moved.
+    /// If moving it fails, the subtask still exists where Asana created it.
+    pub async fn create_subtask_at<T: Model>(&mut self, parent_gid: &str, data: Value, position: InsertPosition) -> Result<T, AsanaError> {
+        let task = self.create_subtask::<T>(parent_gid, data).await?;
+        if position != InsertPosition::Default {
+            self.set_parent(task.gid(), Some(parent_gid), position).await?;
+        }
+
+        Ok(task)
+    }
+
+    /// Make a task a subtask of another task at the given position, or a top-level task by passing `None`
+    ///
+    /// Unlike creating a subtask, this takes an `insert_before` / `insert_after` position among the other subtasks:
+    /// ```
+    /// # use asana_sdk::*;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let mut asana = Asana::builder(String::from("1/your:personal-access-token")).dry_run(true).build();
+    ///
+    /// match asana.set_parent("3333", Some("1111"), InsertPosition::After(String::from("2222"))).await {
+    ///     Err(AsanaError::DryRun(request)) => {
+    ///         assert_eq!(request.url, "https://app.asana.com/api/1.0/tasks/3333/setParent");
+    ///         assert_eq!(request.body, Some(serde_json::json!({ "data": { "parent": "1111", "insert_after": "2222" } })));
+    ///     }
+    ///     _ => panic!("expected a dry run"),
+    /// }
+    ///
+    /// match asana.set_parent("3333", Some("1111"), InsertPosition::Start).await {
+    ///     Err(AsanaError::DryRun(request)) => {
+    ///         assert_eq!(request.body, Some(serde_json::json!({ "data": { "parent": "1111", "insert_after": null } })));
+    ///     }
+    ///     _ => panic!("expected a dry run"),
+    /// }
+    /// # }
+    /// ```
+    pub async fn set_parent(&mut self, task_gid: &str, parent_gid: Option<&str>, position: InsertPosition) -> Result<(), AsanaError> {
+        let mut body = Map::new();
+        body.insert("parent".to_string(), json!(parent_gid));
+        position.apply(&mut body);
+
+        self.action("tasks", task_gid, "setParent", Value::Object(body)).await?;
+        Ok(())
+    }
+
+    /// Like a task, returning its updated number of likes
+    pub async fn like_task(&mut self, task_gid: &str) -> Result<Option<u32>, AsanaError> {
+        self.set_liked("tasks", task_gid, true).await
+    }
+
+    /// Remove the like of the authenticated user from a task, returning its updated number of likes
+    pub async fn unlike_task(&mut self, task_gid: &str) -> Result<Option<u32>, AsanaError> {
+        self.set_liked("tasks", task_gid, false).await
+    }
+
+    /// Like a story (e.g. a comment), returning its updated number of likes
+    pub async fn like_story(&mut self, story_gid: &str) -> Result<Option<u32>, AsanaError> {
+        self.set_liked("stories", story_gid, true).await
+    }
+
+    /// Remove the like of the authenticated user from a story, returning its updated number of likes
+    pub async fn unlike_story(&mut self, story_gid: &str) -> Result<Option<u32>, AsanaError> {
+        self.set_liked("stories", story_gid, false).await
+    }
+
+    async fn set_liked(&mut self, endpoint: &str, gid: &str, liked: bool) -> Result<Option<u32>, AsanaError> {
+        let path = format!("{}/{}", endpoint, gid);
+        let response = self
+            .request(Method::PUT, &path, Some(String::from("num_likes")), Some(json!({ "liked": liked }))).await;
+        self.invalidate_cached_path(&path);
+        let likes: Wrapper<Likes> = parse(response?).await?;
+
+        Ok(likes.data.num_likes)
+    }
+}
+
+#[derive(Deserialize)]
+struct Likes {
+    #[serde(default, deserialize_with = "crate::de::number_or_string")]
+    num_likes: Option<u32>,
+}
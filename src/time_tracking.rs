@@ -0,0 +1,36 @@
+use crate::models::*;
+use crate::{AsanaError, Client};
+use chrono::NaiveDate;
+use serde_json::json;
+
+impl Client {
+    /// List all time tracking entries logged on a task
+    ///
+    /// The model should use the `time_tracking_entries` endpoint:
+    /// ```no_run
+    /// # use asana_sdk::*;
+    /// # use asana_sdk::models::Model;
+    /// # async fn example() -> Result<(), AsanaError> {
+    /// # let mut asana = Asana::connect(String::from("1/your:personal-access-token"));
+    /// model!(TimeEntry "time_tracking_entries" {
+    ///     duration_minutes: u32,
+    ///     entered_on: String,
+    /// });
+    ///
+    /// let entries = asana.time_entries::<TimeEntry>("12345678").await?;
+    /// let minutes: u32 = entries.iter().map(|entry| entry.duration_minutes()).sum();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn time_entries<T: Model>(&mut self, task_gid: &str) -> Result<Vec<T>, AsanaError> {
+        self.scope("tasks", task_gid).list_all::<T>().await
+    }
+
+    /// Log time spent on a task, on the given day
+    pub async fn create_time_entry<T: Model>(&mut self, task_gid: &str, duration_minutes: u32, entered_on: NaiveDate) -> Result<T, AsanaError> {
+        self.scope("tasks", task_gid).create::<T>(json!({
+            "duration_minutes": duration_minutes,
+            "entered_on": entered_on.format("%Y-%m-%d").to_string(),
+        })).await
+    }
+}
@@ -0,0 +1,115 @@
+use crate::models::*;
+use crate::{AsanaError, Client};
+
+/// The kind of resource an Asana web url points to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceKind {
+    Task,
+    Project,
+    Portfolio,
+    Goal,
+}
+
+impl ResourceKind {
+    /// The API endpoint for this kind of resource
+    pub fn endpoint(&self) -> &'static str {
+        match self {
+            ResourceKind::Task => "tasks",
+            ResourceKind::Project => "projects",
+            ResourceKind::Portfolio => "portfolios",
+            ResourceKind::Goal => "goals",
+        }
+    }
+}
+
+/// Extract the resource kind and gid from an Asana web url, as pasted by users
+///
+/// Both the classic `/0/{project}/{task}` format and the newer `/1/{workspace}/project/{project}/task/{task}` format are understood.
+///
+/// ```
+/// use asana_sdk::{parse_asana_url, ResourceKind};
+///
+/// assert_eq!(
+///     parse_asana_url("https://app.asana.com/0/1111/2222/f"),
+///     Some((ResourceKind::Task, String::from("2222")))
+/// );
+/// assert_eq!(
+///     parse_asana_url("https://app.asana.com/0/1111/list"),
+///     Some((ResourceKind::Project, String::from("1111")))
+/// );
+/// assert_eq!(
+///     parse_asana_url("https://app.asana.com/1/9999/project/1111/task/2222?focus=true"),
+///     Some((ResourceKind::Task, String::from("2222")))
+/// );
+/// assert_eq!(parse_asana_url("https://example.com/0/1111/2222"), None);
+/// ```
+pub fn parse_asana_url(url: &str) -> Option<(ResourceKind, String)> {
+    let url = url.trim();
+    let url = url.strip_prefix("https://").or_else(|| url.strip_prefix("http://")).unwrap_or(url);
+    let url = url.split(['?', '#']).next()?;
+
+    let mut segments = url.split('/').filter(|s| !s.is_empty());
+    if segments.next()? != "app.asana.com" {
+        return None;
+    }
+
+    let segments: Vec<&str> = segments.collect();
+    match segments.as_slice() {
+        ["0", "portfolio", gid, ..] if is_gid(gid) => Some((ResourceKind::Portfolio, gid.to_string())),
+        ["0", "goal", gid, ..] if is_gid(gid) => Some((ResourceKind::Goal, gid.to_string())),
+        ["0", project, task, ..] if is_gid(project) && is_gid(task) && project != task => {
+            Some((ResourceKind::Task, task.to_string()))
+        }
+        ["0", project, ..] if is_gid(project) && *project != "0" => Some((ResourceKind::Project, project.to_string())),
+        ["1", workspace, rest @ ..] if is_gid(workspace) => parse_keyed(rest),
+        _ => None,
+    }
+}
+
+// Newer urls name each resource before its gid, the most specific (a task) wins
+fn parse_keyed(segments: &[&str]) -> Option<(ResourceKind, String)> {
+    let mut found = None;
+
+    for pair in segments.windows(2) {
+        let kind = match pair[0] {
+            "task" => ResourceKind::Task,
+            "project" => ResourceKind::Project,
+            "portfolio" => ResourceKind::Portfolio,
+            "goal" => ResourceKind::Goal,
+            _ => continue,
+        };
+
+        if is_gid(pair[1]) && !matches!(found, Some((ResourceKind::Task, _))) {
+            found = Some((kind, pair[1]));
+        }
+    }
+
+    found.map(|(kind, gid)| (kind, gid.to_string()))
+}
+
+fn is_gid(segment: &str) -> bool {
+    !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit())
+}
+
+impl Client {
+    /// Get the entity a pasted Asana url points to, following redirects first for short or share links
+    ///
+    /// Redirects are followed without the access token, so it isn't sent to other hosts.
+    /// Fails with `AsanaError::Validation` if the url doesn't lead to an entity of the model's endpoint.
+    pub async fn get_by_url<T: Model>(&mut self, url: &str) -> Result<T, AsanaError> {
+        let (kind, gid) = match parse_asana_url(url) {
+            Some(resource) => resource,
+            None => {
+                let response = self.client.get(url.trim()).send().await?;
+                parse_asana_url(response.url().as_str())
+                    .ok_or_else(|| AsanaError::Validation(format!("{} doesn't lead to an Asana resource", url)))?
+            }
+        };
+
+        if kind.endpoint() != T::endpoint() {
+            return Err(AsanaError::Validation(format!("{} points to {}, not {}", url, kind.endpoint(), T::endpoint())));
+        }
+
+        self.get::<T>(&gid).await
+    }
+}
@@ -0,0 +1,83 @@
+use crate::models::*;
+use crate::{opt_fields, parse, AsanaError, Client};
+use chrono::{DateTime, SecondsFormat, Utc};
+use reqwest::{Method, StatusCode};
+
+/// Which tasks to list by completion state, sent as the `completed_since` parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletedFilter {
+    /// Both incomplete & completed tasks
+    All,
+    /// Only tasks which are still incomplete
+    IncompleteOnly,
+    /// Incomplete tasks, and tasks completed since the given time
+    CompletedSince(DateTime<Utc>),
+}
+
+impl CompletedFilter {
+    fn param(&self) -> String {
+        match self {
+            CompletedFilter::All => String::from("1970-01-01T00:00:00.000Z"),
+            CompletedFilter::IncompleteOnly => String::from("now"),
+            CompletedFilter::CompletedSince(since) => since.to_rfc3339_opts(SecondsFormat::Millis, true),
+        }
+    }
+}
+
+impl Client {
+    /// List the tasks in a user's "My Tasks" list within a workspace
+    ///
+    /// This first resolves the user's task list for the workspace, and then lists all the tasks within it, following every page.
+    /// The completion filter is always sent, as Asana's default for this list is hard to predict.
+    pub async fn my_tasks<T: Model>(&mut self, user_gid: &str, workspace_gid: &str, completed: CompletedFilter) -> Result<Vec<T>, AsanaError> {
+        let path = format!("users/{}/user_task_list", user_gid);
+        let response = self
+            .param("workspace", workspace_gid)
+            .request(Method::GET, &path, Some(opt_fields::<UserTaskList>(&[])), None).await?;
+        let list: Wrapper<UserTaskList> = parse(response).await?;
+
+        self.param("completed_since", &completed.param())
+            .from::<UserTaskList>(list.data.gid())
+            .list_all::<T>().await
+    }
+
+    /// Resolve `"me"`, an email address or a gid into the canonical gid of a user
+    ///
+    /// Emails are looked up directly first, falling back to a typeahead search in the workspace.
+    pub async fn resolve_user(&mut self, workspace_gid: &str, identifier: &str) -> Result<String, AsanaError> {
+        let identifier = identifier.trim();
+        if !identifier.is_empty() && identifier.bytes().all(|b| b.is_ascii_digit()) {
+            return Ok(identifier.to_string());
+        }
+
+        let path = format!("users/{}", identifier);
+        let error = match self.request(Method::GET, &path, None, None).await {
+            Ok(response) => return Ok(parse::<Wrapper<Compact>>(response).await?.data.gid),
+            Err(error) => error,
+        };
+
+        match error {
+            AsanaError::Api { status: StatusCode::NOT_FOUND, .. } if identifier.contains('@') => {
+                let path = format!("workspaces/{}/typeahead", workspace_gid);
+                let response = self
+                    .param("resource_type", "user")
+                    .param("query", identifier)
+                    .request(Method::GET, &path, Some(String::from("email")), None).await?;
+                let users: ListWrapper<UserEmail> = parse(response).await?;
+
+                users.data.into_iter()
+                    .find(|user| user.email.as_deref().is_some_and(|email| email.eq_ignore_ascii_case(identifier)))
+                    .map(|user| user.gid)
+                    .ok_or(error)
+            }
+            error => Err(error),
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct UserEmail {
+    gid: String,
+    #[serde(default)]
+    email: Option<String>,
+}
@@ -0,0 +1,152 @@
+use crate::models::*;
+use crate::{AsanaError, Client, CompletedFilter, Pages};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{json, Value};
+
+/// Filters for listing a workspace's audit log events, all of which are optional
+#[derive(Debug, Clone, Default)]
+pub struct AuditLogQuery {
+    /// Only events created at or after this moment
+    pub start_at: Option<DateTime<Utc>>,
+    /// Only events created before this moment
+    pub end_at: Option<DateTime<Utc>>,
+    /// Only events of this type, e.g. `user_login_succeeded`
+    pub event_type: Option<String>,
+    /// Only events by this type of actor: `user`, `asana`, `asana_support`, `anonymous` or `external_administrator`
+    pub actor_type: Option<String>,
+    /// Only events by this actor
+    pub actor_gid: Option<String>,
+    /// Only events affecting this resource
+    pub resource_gid: Option<String>,
+}
+
+impl AuditLogQuery {
+    fn params(&self) -> Vec<(&'static str, String)> {
+        let timestamp = |t: &DateTime<Utc>| t.to_rfc3339_opts(SecondsFormat::Millis, true);
+
+        vec![
+            ("start_at", self.start_at.as_ref().map(timestamp)),
+            ("end_at", self.end_at.as_ref().map(timestamp)),
+            ("event_type", self.event_type.clone()),
+            ("actor_type", self.actor_type.clone()),
+            ("actor_gid", self.actor_gid.clone()),
+            ("resource_gid", self.resource_gid.clone()),
+        ].into_iter().filter_map(|(key, value)| value.map(|value| (key, value))).collect()
+    }
+}
+
+/// A view on a client which defaults calls to a single workspace, created with `asana.workspace(gid)`
+///
+/// Lists go through the workspace's relational endpoints (`/workspaces/{gid}/projects`),
+/// and created entities are placed in the workspace, without changing any state on the client itself.
+pub struct Workspace<'a> {
+    client: &'a mut Client,
+    gid: String,
+}
+
+impl<'a> Workspace<'a> {
+    pub fn gid(&self) -> &str {
+        &self.gid
+    }
+
+    pub async fn list<T: Model>(&mut self) -> Result<Vec<T>, AsanaError> {
+        self.client.scope("workspaces", &self.gid).list::<T>().await
+    }
+
+    pub async fn list_all<T: Model>(&mut self) -> Result<Vec<T>, AsanaError> {
+        self.client.scope("workspaces", &self.gid).list_all::<T>().await
+    }
+
+    pub fn pages<T: Model>(&mut self) -> Pages<'_, T> {
+        self.client.scope("workspaces", &self.gid).pages::<T>()
+    }
+
+    /// Create an entity in this workspace, unless the body already names a `workspace`
+    pub async fn create<T: Model>(&mut self, mut data: Value) -> Result<T, AsanaError> {
+        if let Value::Object(fields) = &mut data {
+            fields.entry("workspace").or_insert_with(|| json!(self.gid));
+        }
+
+        self.client.create::<T>(data).await
+    }
+
+    /// List the tasks in a user's "My Tasks" list within this workspace
+    pub async fn my_tasks<T: Model>(&mut self, user_gid: &str, completed: CompletedFilter) -> Result<Vec<T>, AsanaError> {
+        self.client.my_tasks::<T>(user_gid, &self.gid, completed).await
+    }
+}
+
+impl Client {
+    /// Page through the audit log events of a workspace (an Enterprise feature, requiring a service account token)
+    ///
+    /// Audit logs can be very large, so this returns a pager rather than collecting every event.
+    pub fn audit_log_events<T: Model>(&mut self, workspace_gid: &str, filters: AuditLogQuery) -> Pages<'_, T> {
+        for (key, value) in filters.params() {
+            self.param(key, &value);
+        }
+
+        self.scope("workspaces", workspace_gid).pages::<T>()
+    }
+
+    /// List the definitions of all custom fields in a workspace, e.g. to map custom field gids to their names & types
+    ///
+    /// The model should use the `custom_fields` endpoint:
+    /// ```no_run
+    /// # use asana_sdk::*;
+    /// # use asana_sdk::models::Model;
+    /// # async fn example() -> Result<(), AsanaError> {
+    /// # let mut asana = Asana::connect(String::from("1/your:personal-access-token"));
+    /// model!(CustomField "custom_fields" {
+    ///     name: String,
+    ///     resource_subtype: String,
+    /// });
+    ///
+    /// let fields = asana.custom_field_definitions::<CustomField>("12345678").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn custom_field_definitions<T: Model>(&mut self, workspace_gid: &str) -> Result<Vec<T>, AsanaError> {
+        self.scope("workspaces", workspace_gid).list_all::<T>().await
+    }
+
+    /// List all tags in a workspace, following every page
+    ///
+    /// The model should use the `tags` endpoint, and can declare the tag's `color`:
+    /// ```no_run
+    /// # use asana_sdk::*;
+    /// # use asana_sdk::models::Model;
+    /// # async fn example() -> Result<(), AsanaError> {
+    /// # let mut asana = Asana::connect(String::from("1/your:personal-access-token"));
+    /// model!(Tag "tags" {
+    ///     name: String,
+    ///     color: Option<String>,
+    /// });
+    ///
+    /// let tags = asana.tags_in_workspace::<Tag>("12345678").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn tags_in_workspace<T: Model>(&mut self, workspace_gid: &str) -> Result<Vec<T>, AsanaError> {
+        self.scope("workspaces", workspace_gid).list_all::<T>().await
+    }
+
+    /// Add a user to a workspace by email, inviting them if they don't have an Asana account yet
+    pub async fn invite_user(&mut self, workspace_gid: &str, email: &str) -> Result<(), AsanaError> {
+        self.action("workspaces", workspace_gid, "addUser", json!({ "user": email })).await?;
+        Ok(())
+    }
+
+    /// Remove a user from a workspace, which also removes them from its teams & projects
+    pub async fn remove_user(&mut self, workspace_gid: &str, user_gid: &str) -> Result<(), AsanaError> {
+        self.action("workspaces", workspace_gid, "removeUser", json!({ "user": user_gid })).await?;
+        Ok(())
+    }
+
+    /// Scope calls to a workspace, for apps juggling several workspaces with one client
+    pub fn workspace(&mut self, workspace_gid: &str) -> Workspace<'_> {
+        Workspace {
+            client: self,
+            gid: workspace_gid.to_string(),
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! A minimal local HTTP server standing in for the Asana API
+
+use asana_sdk::{Asana, Client};
+use reqwest::Url;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+
+/// Serves responses from `respond`, which gets the path & query of each request below `/api/1.0/`
+pub struct MockServer {
+    address: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl MockServer {
+    pub fn start(respond: impl Fn(&str) -> serde_json::Value + Send + 'static) -> MockServer {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let received = requests.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                }
+
+                let target = request_line.split_whitespace().nth(1).unwrap_or_default();
+                let target = target.trim_start_matches("/api/1.0/").to_string();
+                let body = respond(&target).to_string();
+                received.lock().unwrap().push(target);
+
+                write!(stream, "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", body.len(), body).unwrap();
+            }
+        });
+
+        MockServer { address, requests }
+    }
+
+    /// A client which sends every request to this server instead of Asana
+    pub fn client(&self) -> Client {
+        let address = self.address.clone();
+        Asana::builder(String::from("1/test:token"))
+            .middleware(move |mut request| {
+                let url = request.url();
+                let mocked = format!("http://{}{}?{}", address, url.path(), url.query().unwrap_or_default());
+                *request.url_mut() = Url::parse(&mocked).unwrap();
+                request
+            })
+            .build()
+    }
+
+    /// The path & query of every request received so far
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
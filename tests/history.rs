@@ -0,0 +1,33 @@
+mod common;
+
+use asana_sdk::model;
+use asana_sdk::models::Model;
+use common::MockServer;
+use serde_json::json;
+
+model!(Task "tasks" { name: String });
+model!(Story "stories" { text: String });
+
+#[tokio::test]
+async fn task_with_history_follows_every_page_of_stories() {
+    let server = MockServer::start(|target| {
+        if target.starts_with("tasks/1/stories/") && target.contains("offset=page-2") {
+            json!({ "data": [{ "gid": "12", "resource_type": "story", "text": "Second page" }] })
+        } else if target.starts_with("tasks/1/stories/") {
+            json!({
+                "data": [{ "gid": "11", "resource_type": "story", "text": "First page" }],
+                "next_page": { "offset": "page-2", "path": "/tasks/1/stories?offset=page-2", "uri": "" },
+            })
+        } else {
+            json!({ "data": { "gid": "1", "resource_type": "task", "name": "Busy task" } })
+        }
+    });
+
+    let mut asana = server.client();
+    let (task, stories) = asana.task_with_history::<Task, Story>("1").await.unwrap();
+
+    assert_eq!(task.gid(), "1");
+    let gids: Vec<&str> = stories.iter().map(|story| story.gid()).collect();
+    assert_eq!(gids, vec!["11", "12"]);
+    assert_eq!(server.requests().iter().filter(|target| target.starts_with("tasks/1/stories/")).count(), 2);
+}
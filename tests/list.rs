@@ -0,0 +1,23 @@
+mod common;
+
+use asana_sdk::model;
+use asana_sdk::models::Model;
+use common::MockServer;
+use serde_json::json;
+
+model!(Tag "tags" { name: String });
+
+#[tokio::test]
+async fn list_checked_requests_a_page_and_reports_more() {
+    let server = MockServer::start(|_| json!({
+        "data": [{ "gid": "1", "resource_type": "tag", "name": "Urgent" }],
+        "next_page": { "offset": "page-2", "path": "/tags?offset=page-2", "uri": "" },
+    }));
+
+    let mut asana = server.client();
+    let (tags, more) = asana.list_checked::<Tag>().await.unwrap();
+
+    assert_eq!(tags[0].gid(), "1");
+    assert!(more);
+    assert!(server.requests()[0].contains("limit=100"), "limit missing from {}", server.requests()[0]);
+}
@@ -0,0 +1,18 @@
+mod common;
+
+use common::MockServer;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn presets_request_well_formed_fields() {
+    let server = MockServer::start(|_| json!({ "data": { "gid": "1", "resource_type": "user" } }));
+
+    let mut asana = server.client();
+    asana.register_preset("empty", &[]).register_preset("repeated", &["name", "resource_type", "name"]);
+    let _: Value = asana.get_preset("users", "1", "empty").await.unwrap();
+    let _: Value = asana.get_preset("users", "1", "repeated").await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0], "users/1?opt_fields=this.%28resource_type%29");
+    assert_eq!(requests[1], "users/1?opt_fields=this.%28resource_type%7Cname%29");
+}
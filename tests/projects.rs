@@ -0,0 +1,46 @@
+mod common;
+
+use asana_sdk::model;
+use asana_sdk::models::Model;
+use common::MockServer;
+use serde_json::json;
+
+model!(Project "projects" { name: String });
+
+#[tokio::test]
+async fn all_projects_filters_archived_and_follows_every_page() {
+    let server = MockServer::start(|target| {
+        if target.contains("offset=page-2") {
+            json!({ "data": [{ "gid": "2", "resource_type": "project", "name": "Second page" }] })
+        } else {
+            json!({
+                "data": [{ "gid": "1", "resource_type": "project", "name": "First page" }],
+                "next_page": { "offset": "page-2", "path": "/workspaces/9/projects?offset=page-2", "uri": "" },
+            })
+        }
+    });
+
+    let mut asana = server.client();
+    let projects = asana.all_projects::<Project>("9", Some(false)).await.unwrap();
+
+    let gids: Vec<&str> = projects.iter().map(|project| project.gid()).collect();
+    assert_eq!(gids, vec!["1", "2"]);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    for target in &requests {
+        assert!(target.starts_with("workspaces/9/projects/?"), "unexpected request {}", target);
+        assert!(target.contains("archived=false"), "archived filter missing from {}", target);
+    }
+}
+
+#[tokio::test]
+async fn all_projects_without_filter_sends_no_archived_param() {
+    let server = MockServer::start(|_| json!({ "data": [] }));
+
+    let mut asana = server.client();
+    let projects = asana.all_projects::<Project>("9", None).await.unwrap();
+
+    assert!(projects.is_empty());
+    assert!(!server.requests()[0].contains("archived"));
+}